@@ -319,12 +319,17 @@ pub mod itoap {
     }
     use common::*;
 
-    #[cfg(not(all(
-        any(target_arch = "x86_64", target_arch = "x86"),
-        target_feature = "sse2",
-        not(miri),
-    )))]
-    mod fallback {
+    // also compiled under test, so both code paths can be verified on sse2 targets
+    #[cfg(any(
+        test,
+        not(all(
+            any(target_arch = "x86_64", target_arch = "x86"),
+            target_feature = "sse2",
+            not(miri),
+        ))
+    ))]
+    #[cfg_attr(test, allow(dead_code))]
+    pub(crate) mod fallback {
         use core::ptr;
 
         use super::common::{divmod, lookup, write4, write4_pad, write8_pad};
@@ -402,7 +407,7 @@ pub mod itoap {
         target_feature = "sse2",
         not(miri),
     ))]
-    mod sse2 {
+    pub(crate) mod sse2 {
         #![allow(non_upper_case_globals)]
 
         #[cfg(target_arch = "x86")]
@@ -770,6 +775,128 @@ impl<'a, W: std::io::Write> CustomBufWriter<'a, W> {
         }
     }
 
+    /// unsafely add a float rounded to exactly `decimals` digits after the point, `decimals` can be at most 19.
+    /// output is identical to `format!("{:.decimals$}", float)`, including round-half-to-even on the exact binary value.
+    /// call `maybe_flush()` first if you think it may overflow, this writes at most 311 + `decimals` bytes
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn add_float(&mut self, float: f64, decimals: usize) {
+        /// 10<sup>19</sup> is the largest power of 10 which fits in a u64
+        const POW10: [u64; 20] = {
+            let mut pow10 = [1; 20];
+            let mut idx = 1;
+            while idx < 20 {
+                pow10[idx] = pow10[idx - 1] * 10;
+                idx += 1;
+            }
+            pow10
+        };
+
+        if float.is_nan() {
+            self.add_bytes(b"NaN");
+            return;
+        }
+        if float.is_sign_negative() {
+            self.add_byte(b'-');
+        }
+        if float.is_infinite() {
+            self.add_bytes(b"inf");
+            return;
+        }
+
+        // float = mantissa * 2^exponent, exactly
+        let bits = float.to_bits();
+        let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+        let (mantissa, exponent) = if biased_exponent == 0 {
+            (bits & 0xf_ffff_ffff_ffff, -1074)
+        } else {
+            (bits & 0xf_ffff_ffff_ffff | 1 << 52, biased_exponent - 1075)
+        };
+
+        if exponent >= 0 {
+            // no fractional part, mantissa * 2^74 still fits in a u128
+            if exponent < 75 {
+                self.add_int(u128::from(mantissa) << exponent);
+            } else {
+                self.add_big_int(mantissa, exponent as u32);
+            }
+            if decimals != 0 {
+                self.add_byte(b'.');
+                self.add_padded(0, decimals);
+            }
+            return;
+        }
+
+        // scaled < 2^53 * 10^19 < 2^117, so shifting further than that always rounds to 0
+        let pow = u128::from(POW10[decimals]);
+        let scaled = u128::from(mantissa) * pow;
+        let shift = exponent.unsigned_abs();
+        let rounded = if shift > 117 {
+            0
+        } else {
+            let quotient = scaled >> shift;
+            let remainder = scaled & ((1 << shift) - 1);
+            let half = 1 << (shift - 1);
+            quotient + u128::from(remainder > half || (remainder == half && quotient & 1 == 1))
+        };
+
+        self.add_int(rounded / pow);
+        if decimals != 0 {
+            self.add_byte(b'.');
+            self.add_padded((rounded % pow) as u64, decimals);
+        }
+    }
+
+    /// write `mantissa * 2^exponent` for integers too large for a u128, using a stack-allocated bignum
+    #[allow(clippy::cast_possible_truncation)]
+    fn add_big_int(&mut self, mantissa: u64, exponent: u32) {
+        /// 10^9 is the largest power of 10 which fits in a u32 limb
+        const CHUNK: u64 = 1_000_000_000;
+
+        // an f64 can be at most 2^1024, which fits in 33 32-bit limbs
+        let mut limbs = [0_u32; 33];
+        let (limb, offset) = ((exponent / 32) as usize, exponent % 32);
+        let shifted = u128::from(mantissa) << offset;
+        limbs[limb] = shifted as u32;
+        limbs[limb + 1] = (shifted >> 32) as u32;
+        limbs[limb + 2] = (shifted >> 64) as u32;
+        let mut len = limb + 3;
+
+        // at most 309 digits, so 35 chunks of 9 digits
+        let mut chunks = [0_u32; 35];
+        let mut chunk_len = 0;
+        while len != 0 {
+            let mut remainder = 0;
+            for limb in limbs[..len].iter_mut().rev() {
+                let current = remainder << 32 | u64::from(*limb);
+                *limb = (current / CHUNK) as u32;
+                remainder = current % CHUNK;
+            }
+            chunks[chunk_len] = remainder as u32;
+            chunk_len += 1;
+            while len != 0 && limbs[len - 1] == 0 {
+                len -= 1;
+            }
+        }
+
+        self.add_int(chunks[chunk_len - 1]);
+        for &chunk in chunks[..chunk_len - 1].iter().rev() {
+            self.add_padded(u64::from(chunk), 9);
+        }
+    }
+
+    /// unsafely write exactly `width` digits of `value`, left-padded with zeros
+    #[allow(clippy::cast_possible_truncation)]
+    fn add_padded(&mut self, mut value: u64, width: usize) {
+        unsafe {
+            let ptr = self.buffer.as_mut_ptr().add(self.buffer_pointer);
+            for idx in (0..width).rev() {
+                ptr.add(idx).write(b'0' + (value % 10) as u8);
+                value /= 10;
+            }
+            self.buffer_pointer += width;
+        }
+    }
+
     /// unsafely write one character to buffer
     /// `call maybe_flush()` first if you think you might overflow
     pub fn add_byte(&mut self, byte: u8) {
@@ -861,15 +988,16 @@ macro_rules! impl_anyint {
                     digits => (false, digits),
                 };
 
+                // wrapping arithmetic so that the minimum value (whose magnitude overflows) still parses
                 let result = unsafe {
                     digits.iter()
                         .map(|byte| (byte & 15) as $t)
-                        .reduce(|acc, digit| acc * 10 + digit)
+                        .reduce(|acc, digit| acc.wrapping_mul(10).wrapping_add(digit))
                         .unwrap_unchecked()
                 };
 
                 if neg {
-                    -result
+                    result.wrapping_neg()
                 } else {
                     result
                 }
@@ -1030,6 +1158,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &str) {
+        rust_cses::validate::assert_valid("0_cses_template", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
@@ -1060,4 +1189,87 @@ mod test {
 
         test(input, target);
     }
+
+    fn test_float(values: impl IntoIterator<Item = f64>, decimals: usize) {
+        let mut out = vec![];
+        let mut expected = vec![];
+        {
+            let mut writer = CustomBufWriter::new(&mut out);
+            for value in values {
+                writer.maybe_flush(400);
+                writer.add_float(value, decimals);
+                writer.add_byte(b'\n');
+                writeln!(expected, "{value:.decimals$}").unwrap();
+            }
+        }
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            String::from_utf8(expected).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_float_special_values() {
+        let values = [
+            0.0,
+            -0.0,
+            f64::NAN,
+            -f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::MAX,
+            f64::MIN,
+            f64::MIN_POSITIVE,
+            -f64::MIN_POSITIVE,
+            f64::from_bits(1),
+            f64::EPSILON,
+            -1e-7,
+            1e-6,
+            0.999_999_5,
+            9.999_999_5,
+        ];
+        for decimals in 0..=19 {
+            test_float(values, decimals);
+        }
+    }
+
+    #[test]
+    fn test_float_round_half_to_even() {
+        // every k / 2^n is exact in binary, so these hit the tie-breaking rule at low precision
+        let values = (0..4096).flat_map(|k| (0..24).map(move |n| f64::from(k) / f64::from(1 << n)));
+        let values: Vec<f64> = values.flat_map(|v| [v, -v]).collect();
+        for decimals in 0..=19 {
+            test_float(values.iter().copied(), decimals);
+        }
+    }
+
+    #[test]
+    fn test_float_random_bits() {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        for decimals in 0..=19 {
+            test_float((0..20_000).map(|_| f64::from_bits(rng.gen())), decimals);
+        }
+    }
+
+    #[test]
+    fn test_float_random_probabilities() {
+        use rand::Rng;
+
+        let mut rng = rand::thread_rng();
+        for decimals in 0..=19 {
+            test_float((0..20_000).map(|_| rng.gen::<f64>()), decimals);
+            test_float((0..20_000).map(|_| rng.gen_range(-1e20..1e20)), decimals);
+        }
+    }
+
+    #[test]
+    fn test_float_powers_of_two() {
+        let values = (-1074..1024).map(|exp| 2_f64.powi(exp));
+        for decimals in [0, 1, 6, 19] {
+            test_float(values.clone(), decimals);
+        }
+    }
 }
@@ -97,6 +97,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("additional_multiplication_table", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
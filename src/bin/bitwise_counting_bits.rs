@@ -726,6 +726,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("bitwise_counting_bits", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
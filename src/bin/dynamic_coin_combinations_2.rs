@@ -92,6 +92,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("dynamic_coin_combinations_2", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
@@ -225,6 +225,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("geometry_line_segment_intersection", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
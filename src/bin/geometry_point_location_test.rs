@@ -136,7 +136,7 @@ impl_anyint!(for i8, i16, i32, i64, i128, isize);
 /// <ul>
 /// <li>1 ≤ t ≤ 10<sup>5</sup></li>
 /// <li>-10<sup>9</sup> ≤ x<sub>1</sub>, y<sub>1</sub>, x<sub>2</sub>, y<sub>2</sub>, x<sub>3</sub>, y<sub>3</sub> ≤ 10<sup>9</sup></li>
-/// <li>x<sub>1</sub> != x<sub>2</sub>, y<sub>1</sub> != y<sub>2</sub> </li>
+/// <li>x<sub>1</sub> != x<sub>2</sub> or y<sub>1</sub> != y<sub>2</sub> </li>
 /// </ul>
 fn solve<W: std::io::Write>(scan: &[u8], out: &mut W) {
     let mut iter = scan.split(|n| *n <= b' ');
@@ -177,6 +177,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("geometry_point_location_test", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
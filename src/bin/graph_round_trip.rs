@@ -869,6 +869,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &str) {
+        rust_cses::validate::assert_valid("graph_round_trip", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
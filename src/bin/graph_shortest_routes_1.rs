@@ -844,6 +844,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &str) {
+        rust_cses::validate::assert_valid("graph_shortest_routes_1", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
@@ -197,6 +197,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &str) {
+        rust_cses::validate::assert_valid("intro_chessboard_and_queens", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
@@ -743,6 +743,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("intro_tower_of_hanoi", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
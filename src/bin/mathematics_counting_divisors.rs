@@ -744,6 +744,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("mathematics_counting_divisors", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
@@ -724,6 +724,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("range_forest_queries", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
@@ -109,6 +109,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("search_apartments", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
@@ -1,6 +1,3 @@
-#![allow(clippy::missing_panics_doc)]
-#![allow(clippy::must_use_candidate)]
-
 use std::borrow::Borrow;
 use std::cmp::Ordering;
 use std::iter::{FromIterator, FusedIterator};
@@ -42,6 +39,7 @@ where
 //     }
 // }
 
+#[allow(clippy::missing_panics_doc, clippy::must_use_candidate)]
 impl<T> FenwickTree<T> {
     pub fn prefix_sum(&self, index: usize, mut sum: T) -> T
     where
@@ -239,6 +237,7 @@ where
     len: usize,
 }
 
+#[allow(clippy::missing_panics_doc, clippy::must_use_candidate)]
 impl<T: Clone + Ord> IndexSet<T> {
     pub fn new() -> Self {
         Self {
@@ -780,6 +779,7 @@ where
     current_back_iterator: Option<std::slice::Iter<'a, T>>,
 }
 
+#[allow(clippy::missing_panics_doc, clippy::must_use_candidate)]
 impl<'a, T> Iter<'a, T>
 where
     T: Clone + Ord,
@@ -1884,6 +1884,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("search_josephus_problem_2", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
@@ -93,6 +93,7 @@ mod test {
     // NOTE: our implementation prints out an additional space at the end of output
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("search_restaurant_customers", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
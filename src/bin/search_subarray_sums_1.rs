@@ -94,6 +94,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("search_subarray_sums_1", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
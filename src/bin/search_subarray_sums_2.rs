@@ -111,6 +111,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("search_subarray_sums_2", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
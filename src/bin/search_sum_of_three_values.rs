@@ -122,6 +122,7 @@ mod test {
     // 3) finally, we print the position of the remaining number possible in conjunction with 1) and 2)
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("search_sum_of_three_values", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
@@ -81,6 +81,7 @@ mod test {
     // NOTE: While any solution is allowed, we greedily try to obtain the first solution, and print the indexes in increasing order.
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("search_sum_of_two_values", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
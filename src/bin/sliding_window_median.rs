@@ -755,6 +755,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("sliding_window_median", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
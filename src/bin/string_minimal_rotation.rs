@@ -76,6 +76,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("string_minimal_rotation", input);
         let mut out = Vec::with_capacity(target.len());
         solve(&mut input.to_owned(), &mut out);
 
@@ -77,6 +77,7 @@ mod test {
     use super::*;
 
     fn test(input: &[u8], target: &[u8]) {
+        rust_cses::validate::assert_valid("string_string_matching", input);
         let mut out = Vec::with_capacity(target.len());
         solve(input, &mut out);
 
@@ -151,8 +152,8 @@ doggone
     #[test]
     fn test_one_unique() {
         let input = b"\
-AAAACAAACAAAA
-AAACAAA
+aaaacaaacaaaa
+aaacaaa
 ";
         let target = b"\
 2
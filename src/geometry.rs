@@ -0,0 +1,437 @@
+//! Plane geometry on integer points, the starting point of the geometry problems (Point Location Test, Line Segment
+//! Intersection, and the polygon problems in [`polygon`]).
+//!
+//! Every predicate is exact: coordinates are promoted to `i128` before any product, so orientations and intersection
+//! tests don't overflow for coordinates below 2<sup>62</sup> in absolute value, and exact intersection points (whose
+//! numerators are cubic in the coordinates) for coordinates below 2<sup>40</sup>.
+
+pub mod polygon;
+
+use std::ops::{Add, Sub};
+
+/// Integer coordinates which can be promoted to `i128`.
+pub trait Coordinate: Copy + Ord + Into<i128> {}
+
+impl<T: Copy + Ord + Into<i128>> Coordinate for T {}
+
+/// Points are ordered by `x`, then `y`: along a line, this is the order of the points on it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point seen as the vector from the origin.
+pub type Vector<T> = Point<T>;
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+/// Side of a point relative to a directed line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    /// clockwise turn
+    Right,
+    Collinear,
+    /// counterclockwise turn
+    Left,
+}
+
+impl<T: Coordinate> Point<T> {
+    #[must_use]
+    pub fn widen(self) -> Point<i128> {
+        Point::new(self.x.into(), self.y.into())
+    }
+
+    /// z-component of the cross product, positive when `other` is counterclockwise from `self`
+    #[must_use]
+    pub fn cross(self, other: Self) -> i128 {
+        let (a, b) = (self.widen(), other.widen());
+        a.x * b.y - a.y * b.x
+    }
+
+    #[must_use]
+    pub fn dot(self, other: Self) -> i128 {
+        let (a, b) = (self.widen(), other.widen());
+        a.x * b.x + a.y * b.y
+    }
+
+    /// twice the signed area of the triangle `self`, `b`, `c`, positive when it turns counterclockwise
+    #[must_use]
+    pub fn turn(self, b: Self, c: Self) -> i128 {
+        let a = self.widen();
+        (b.widen() - a).cross(c.widen() - a)
+    }
+
+    /// Point Location Test: the side of `c` when looking from `self` to `b`.
+    #[must_use]
+    pub fn orientation(self, b: Self, c: Self) -> Orientation {
+        match self.turn(b, c).cmp(&0) {
+            std::cmp::Ordering::Less => Orientation::Right,
+            std::cmp::Ordering::Equal => Orientation::Collinear,
+            std::cmp::Ordering::Greater => Orientation::Left,
+        }
+    }
+
+    fn exact(self) -> Point<Rational> {
+        let p = self.widen();
+        Point::new(Rational::from(p.x), Rational::from(p.y))
+    }
+}
+
+/// Fraction in lowest terms with a positive denominator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    /// # Panics
+    ///   if `den` is 0
+    #[must_use]
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "zero denominator");
+        let (mut a, mut b) = (num, den);
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        let g = a.abs() * den.signum();
+        Self {
+            num: num / g,
+            den: den / g,
+        }
+    }
+
+    #[must_use]
+    pub fn num(self) -> i128 {
+        self.num
+    }
+
+    #[must_use]
+    pub fn den(self) -> i128 {
+        self.den
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(num: i128) -> Self {
+        Self { num, den: 1 }
+    }
+}
+
+/// Closed segment between two points, possibly equal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Segment<T> {
+    pub start: Point<T>,
+    pub end: Point<T>,
+}
+
+/// Common part of two segments.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Intersection<T> {
+    Point(Point<Rational>),
+    /// collinear segments sharing more than a point, from the smallest common point to the largest
+    Overlap(Segment<T>),
+}
+
+impl<T> Segment<T> {
+    pub const fn new(start: Point<T>, end: Point<T>) -> Self {
+        Self { start, end }
+    }
+}
+
+impl<T: Coordinate> Segment<T> {
+    /// `p` is on the segment, the endpoints included
+    #[must_use]
+    pub fn contains_point(&self, p: Point<T>) -> bool {
+        self.start.orientation(self.end, p) == Orientation::Collinear
+            && self.start.min(self.end) <= p
+            && p <= self.start.max(self.end)
+    }
+
+    /// Line Segment Intersection: the segments have at least one common point.
+    #[must_use]
+    pub fn intersects(&self, other: &Self) -> bool {
+        let (a, b, c, d) = (self.start, self.end, other.start, other.end);
+        // proper crossing, or touching at a point that is not an endpoint of both
+        let crossing = a.orientation(b, c) != a.orientation(b, d)
+            && c.orientation(d, a) != c.orientation(d, b);
+        // the orientations don't tell apart collinear segments
+        crossing
+            || other.contains_point(a)
+            || other.contains_point(b)
+            || self.contains_point(c)
+            || self.contains_point(d)
+    }
+
+    /// The common part of the segments, `None` if they don't intersect.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Option<Intersection<T>> {
+        let (a, c) = (self.start.widen(), other.start.widen());
+        let (ab, cd) = (self.end.widen() - a, other.end.widen() - c);
+        let mut den = ab.cross(cd);
+        if den == 0 {
+            // parallel or degenerate: the common part is bounded by the endpoints lying on the other segment
+            let mut common = [self.start, self.end]
+                .into_iter()
+                .filter(|&e| other.contains_point(e))
+                .chain(
+                    [other.start, other.end]
+                        .into_iter()
+                        .filter(|&e| self.contains_point(e)),
+                );
+            let first = common.next()?;
+            let (low, high) =
+                common.fold((first, first), |(low, high), e| (low.min(e), high.max(e)));
+            return Some(if low == high {
+                Intersection::Point(low.exact())
+            } else {
+                Intersection::Overlap(Segment::new(low, high))
+            });
+        }
+        // a + t / den * ab = c + u / den * cd
+        let (mut t, mut u) = ((c - a).cross(cd), (c - a).cross(ab));
+        if den < 0 {
+            (den, t, u) = (-den, -t, -u);
+        }
+        ((0..=den).contains(&t) && (0..=den).contains(&u)).then(|| {
+            Intersection::Point(Point::new(
+                Rational::new(a.x * den + t * ab.x, den),
+                Rational::new(a.y * den + t * ab.y, den),
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// the check of `geometry_line_segment_intersection`, on a line through the points 1 and 2
+    fn naive_intersects(s: [i64; 8]) -> bool {
+        let point_loc = |i: usize, j: usize, k: usize| {
+            ((s[k + 1] - s[i + 1]) * (s[j] - s[i]) - (s[k] - s[i]) * (s[j + 1] - s[i + 1])).signum()
+        };
+        let between = |i: usize, j: usize, k: usize| {
+            let (lo, hi) = (s[i].min(s[j]), s[i].max(s[j]));
+            let (y_lo, y_hi) = (s[i + 1].min(s[j + 1]), s[i + 1].max(s[j + 1]));
+            point_loc(i, j, k) == 0
+                && (lo..=hi).contains(&s[k])
+                && (y_lo..=y_hi).contains(&s[k + 1])
+        };
+        between(0, 2, 4)
+            || between(0, 2, 6)
+            || between(4, 6, 0)
+            || between(4, 6, 2)
+            || (point_loc(0, 2, 4) != point_loc(0, 2, 6)
+                && point_loc(4, 6, 0) != point_loc(4, 6, 2))
+    }
+
+    /// lattice points of a segment
+    fn lattice(segment: &Segment<i64>) -> Vec<Point<i64>> {
+        let delta = segment.end - segment.start;
+        let (mut a, mut b) = (delta.x.abs(), delta.y.abs());
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        let steps = a.max(1);
+        let step = Point::new(delta.x / steps, delta.y / steps);
+        let mut points: Vec<Point<i64>> = (0..=steps)
+            .map(|k| segment.start + Point::new(step.x * k, step.y * k))
+            .collect();
+        points.sort_unstable();
+        points.dedup();
+        points
+    }
+
+    #[test]
+    fn test_examples() {
+        // Point Location Test
+        let tests = [[1, 1, 5, 3, 2, 3], [1, 1, 5, 3, 4, 1], [1, 1, 5, 3, 3, 2]];
+        let sides: Vec<Orientation> = tests
+            .iter()
+            .map(|t| {
+                Point::new(t[0], t[1]).orientation(Point::new(t[2], t[3]), Point::new(t[4], t[5]))
+            })
+            .collect();
+        assert_eq!(
+            sides,
+            [
+                Orientation::Left,
+                Orientation::Right,
+                Orientation::Collinear
+            ]
+        );
+        // Line Segment Intersection
+        let tests: [[i64; 8]; 5] = [
+            [1, 1, 5, 3, 1, 2, 4, 3],
+            [1, 1, 5, 3, 1, 1, 4, 3],
+            [1, 1, 5, 3, 2, 3, 4, 1],
+            [1, 1, 5, 3, 2, 4, 4, 1],
+            [1, 1, 5, 3, 3, 2, 7, 4],
+        ];
+        let segments = |t: &[i64; 8]| {
+            (
+                Segment::new(Point::new(t[0], t[1]), Point::new(t[2], t[3])),
+                Segment::new(Point::new(t[4], t[5]), Point::new(t[6], t[7])),
+            )
+        };
+        let answers: Vec<bool> = tests
+            .iter()
+            .map(segments)
+            .map(|(first, second)| first.intersects(&second))
+            .collect();
+        assert_eq!(answers, [false, true, true, true, true]);
+
+        let (first, second) = segments(&tests[2]);
+        assert_eq!(
+            first.intersection(&second),
+            Some(Intersection::Point(Point::new(
+                Rational::from(3),
+                Rational::from(2)
+            )))
+        );
+        let (first, second) = segments(&tests[3]);
+        assert_eq!(
+            first.intersection(&second),
+            Some(Intersection::Point(Point::new(
+                Rational::new(13, 4),
+                Rational::new(17, 8)
+            )))
+        );
+        let (first, second) = segments(&tests[4]);
+        assert_eq!(
+            first.intersection(&second),
+            Some(Intersection::Overlap(Segment::new(
+                Point::new(3, 2),
+                Point::new(5, 3)
+            )))
+        );
+        assert_eq!(Point::new(3, 4).dot(Point::new(-4, 3)), 0);
+        assert_eq!(Point::new(1, 0).cross(Point::new(0, 1)), 1);
+        assert_eq!(Rational::new(6, -4), Rational::new(-3, 2));
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..20_000 {
+            let mut coordinates = [0; 8];
+            rng.fill(&mut coordinates);
+            let coordinates = coordinates.map(|c: i64| c.rem_euclid(9) - 4);
+            let first = Segment::new(
+                Point::new(coordinates[0], coordinates[1]),
+                Point::new(coordinates[2], coordinates[3]),
+            );
+            let second = Segment::new(
+                Point::new(coordinates[4], coordinates[5]),
+                Point::new(coordinates[6], coordinates[7]),
+            );
+            let intersects = first.intersects(&second);
+            if first.start != first.end && second.start != second.end {
+                assert_eq!(intersects, naive_intersects(coordinates), "{coordinates:?}");
+            }
+
+            for x in -4..=4 {
+                for y in -4..=4 {
+                    let p = Point::new(x, y);
+                    assert_eq!(first.contains_point(p), lattice(&first).contains(&p));
+                }
+            }
+
+            let common: Vec<Point<i64>> = lattice(&first)
+                .into_iter()
+                .filter(|p| second.contains_point(*p))
+                .collect();
+            match first.intersection(&second) {
+                None => assert!(!intersects),
+                Some(Intersection::Overlap(overlap)) => {
+                    assert!(overlap.start < overlap.end);
+                    assert_eq!(lattice(&overlap), common);
+                }
+                Some(Intersection::Point(point)) => {
+                    assert!(intersects);
+                    // on both lines, within both bounding boxes
+                    for segment in [first, second] {
+                        let (start, end) = (segment.start.widen(), segment.end.widen());
+                        let (x, y) = (point.x, point.y);
+                        assert_eq!(
+                            (x.num() - start.x * x.den()) * y.den() * (end.y - start.y),
+                            (y.num() - start.y * y.den()) * x.den() * (end.x - start.x)
+                        );
+                        assert!(
+                            start.x.min(end.x) * x.den() <= x.num()
+                                && x.num() <= start.x.max(end.x) * x.den()
+                        );
+                        assert!(
+                            start.y.min(end.y) * y.den() <= y.num()
+                                && y.num() <= start.y.max(end.y) * y.den()
+                        );
+                    }
+                    let integral = (point.x.den() == 1 && point.y.den() == 1)
+                        .then(|| Point::new(point.x.num(), point.y.num()));
+                    assert_eq!(common.first().map(|p| p.widen()), integral);
+                }
+            }
+            assert_eq!(
+                first.intersection(&second).map(|_| ()),
+                second.intersection(&first).map(|_| ())
+            );
+        }
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        // the products of differences don't fit in i64 here
+        let far = (1_i64 << 62) - 1;
+        let (a, b) = (Point::new(-far, -far), Point::new(far, far));
+        assert_eq!(a.orientation(b, Point::new(0, 1)), Orientation::Left);
+        assert_eq!(a.orientation(b, Point::new(1, 0)), Orientation::Right);
+        assert_eq!(
+            a.orientation(b, Point::new(-far, -far)),
+            Orientation::Collinear
+        );
+        assert_eq!(
+            a.orientation(b, Point::new(far - 1, far)),
+            Orientation::Left
+        );
+        let diagonal = Segment::new(a, b);
+        assert!(diagonal.contains_point(Point::new(far - 1, far - 1)));
+        assert!(!diagonal.contains_point(Point::new(far - 1, far)));
+        assert!(diagonal.intersects(&Segment::new(Point::new(far, -far), Point::new(-far, far))));
+        assert!(!diagonal.intersects(&Segment::new(Point::new(far, 1 - far), Point::new(1, 0))));
+
+        let big = 1_000_000_000_i64;
+        let first = Segment::new(Point::new(0, 0), Point::new(big, 3));
+        let second = Segment::new(Point::new(0, 1), Point::new(big, 0));
+        assert_eq!(
+            first.intersection(&second),
+            Some(Intersection::Point(Point::new(
+                Rational::from(250_000_000),
+                Rational::new(3, 4)
+            )))
+        );
+    }
+}
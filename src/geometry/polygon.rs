@@ -0,0 +1,226 @@
+//! Simple polygons given by their vertices in order, in either direction: Polygon Area, Point in Polygon and Polygon
+//! Lattice Points.
+//!
+//! Areas are kept doubled so that they stay integers. A point is located by its winding number, once the edges have
+//! been checked for the boundary. Lattice points come from Pick's theorem: 2A = 2I + B - 2.
+
+use super::{Coordinate, Orientation, Point, Segment};
+
+/// Location of a point relative to a polygon.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Location {
+    Inside,
+    Outside,
+    Boundary,
+}
+
+/// edges of the polygon, the last one closing it
+fn edges<T: Coordinate>(vertices: &[Point<T>]) -> impl Iterator<Item = Segment<T>> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&start, &end)| Segment::new(start, end))
+}
+
+/// Twice the signed area (shoelace formula), positive when the vertices go counterclockwise: Polygon Area is its
+/// absolute value.
+#[must_use]
+pub fn doubled_area<T: Coordinate>(vertices: &[Point<T>]) -> i128 {
+    edges(vertices).map(|edge| edge.start.cross(edge.end)).sum()
+}
+
+/// Point in Polygon: where `p` is, in O(n).
+#[must_use]
+pub fn locate<T: Coordinate>(vertices: &[Point<T>], p: Point<T>) -> Location {
+    let mut winding = 0;
+    for edge in edges(vertices) {
+        if edge.contains_point(p) {
+            return Location::Boundary;
+        }
+        // the edges crossing the horizontal line through p, each counted at its lower end only
+        let (start, end) = (edge.start, edge.end);
+        if start.y <= p.y && p.y < end.y && start.orientation(end, p) == Orientation::Left {
+            winding += 1;
+        } else if end.y <= p.y && p.y < start.y && start.orientation(end, p) == Orientation::Right {
+            winding -= 1;
+        }
+    }
+    if winding == 0 {
+        Location::Outside
+    } else {
+        Location::Inside
+    }
+}
+
+/// number of lattice points on the boundary
+#[must_use]
+pub fn boundary_points<T: Coordinate>(vertices: &[Point<T>]) -> u128 {
+    edges(vertices)
+        .map(|edge| {
+            let delta = edge.end.widen() - edge.start.widen();
+            let (mut a, mut b) = (delta.x.unsigned_abs(), delta.y.unsigned_abs());
+            while b != 0 {
+                (a, b) = (b, a % b);
+            }
+            a
+        })
+        .sum()
+}
+
+/// Polygon Lattice Points: the number of lattice points strictly inside and on the boundary.
+#[must_use]
+pub fn lattice_points<T: Coordinate>(vertices: &[Point<T>]) -> (u128, u128) {
+    let boundary = boundary_points(vertices);
+    // Pick's theorem, a polygon has at least 3 boundary points
+    let interior = (doubled_area(vertices).unsigned_abs() + 2).saturating_sub(boundary) / 2;
+    (interior, boundary)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn points(coordinates: &[(i64, i64)]) -> Vec<Point<i64>> {
+        coordinates.iter().map(|&(x, y)| Point::new(x, y)).collect()
+    }
+
+    /// A star-shaped polygon around `center`: random points by angle, each turning by less than a half-turn, so that
+    /// the triangles from `center` to the edges tile it.
+    fn random_star(rng: &mut StdRng, size: i64, center: Point<i64>) -> Option<Vec<Point<i64>>> {
+        let mut vertices: Vec<Point<i64>> = (0..rng.gen_range(3..=8))
+            .map(|_| Point::new(rng.gen_range(0..=size), rng.gen_range(0..=size)))
+            .filter(|&p| p != center)
+            .collect();
+        let half = |p: Point<i64>| (p.y, p.x) < (center.y, center.x);
+        vertices.sort_by(|&a, &b| {
+            half(a)
+                .cmp(&half(b))
+                .then_with(|| 0.cmp(&center.turn(a, b)))
+        });
+        vertices.dedup_by(|a, b| center.turn(*a, *b) == 0 && half(*a) == half(*b));
+        let turning = vertices.len() >= 3
+            && edges(&vertices).all(|edge| center.turn(edge.start, edge.end) > 0);
+        turning.then_some(vertices)
+    }
+
+    /// closed triangle
+    fn in_triangle(a: Point<i64>, b: Point<i64>, c: Point<i64>, p: Point<i64>) -> bool {
+        a.turn(b, p) >= 0 && b.turn(c, p) >= 0 && c.turn(a, p) >= 0
+    }
+
+    /// `p` is a lattice point of the edge
+    fn on_edge(edge: &Segment<i64>, p: Point<i64>) -> bool {
+        let delta = edge.end - edge.start;
+        let steps = (0..=delta.x.abs().max(delta.y.abs()))
+            .rev()
+            .find(|&k| k > 0 && delta.x % k == 0 && delta.y % k == 0)
+            .unwrap_or(1);
+        (0..=steps).any(|k| edge.start + Point::new(delta.x / steps * k, delta.y / steps * k) == p)
+    }
+
+    #[test]
+    fn test_examples() {
+        // Polygon Area
+        let polygon = points(&[(1, 1), (4, 2), (3, 5), (1, 4)]);
+        assert_eq!(doubled_area(&polygon), 16);
+        let reversed: Vec<Point<i64>> = polygon.iter().rev().copied().collect();
+        assert_eq!(doubled_area(&reversed), -16);
+        // Point in Polygon
+        for vertices in [&polygon, &reversed] {
+            let locations: Vec<Location> = points(&[(2, 3), (3, 1), (1, 3)])
+                .into_iter()
+                .map(|p| locate(vertices, p))
+                .collect();
+            assert_eq!(
+                locations,
+                [Location::Inside, Location::Outside, Location::Boundary]
+            );
+        }
+        // Polygon Lattice Points
+        assert_eq!(lattice_points(&polygon), (6, 6));
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut checked = 0;
+        while checked < 2000 {
+            let size = rng.gen_range(2..=8);
+            let center = Point::new(rng.gen_range(0..=size), rng.gen_range(0..=size));
+            let Some(mut vertices) = random_star(&mut rng, size, center) else {
+                continue;
+            };
+            checked += 1;
+            let fan: Vec<(Point<i64>, Point<i64>)> = edges(&vertices)
+                .map(|edge| (edge.start, edge.end))
+                .collect();
+            let area: i128 = fan.iter().map(|&(a, b)| center.turn(a, b)).sum();
+            if rng.gen() {
+                vertices.reverse();
+            }
+            let shift = rng.gen_range(0..vertices.len());
+            vertices.rotate_left(shift);
+            assert_eq!(doubled_area(&vertices).abs(), area);
+
+            let (mut interior, mut boundary) = (0, 0);
+            for x in -1..=size + 1 {
+                for y in -1..=size + 1 {
+                    let p = Point::new(x, y);
+                    let naive = if edges(&vertices).any(|edge| on_edge(&edge, p)) {
+                        boundary += 1;
+                        Location::Boundary
+                    } else if fan.iter().any(|&(a, b)| in_triangle(center, a, b, p)) {
+                        interior += 1;
+                        Location::Inside
+                    } else {
+                        Location::Outside
+                    };
+                    assert_eq!(locate(&vertices, p), naive, "{vertices:?} {p:?}");
+                }
+            }
+            assert_eq!(lattice_points(&vertices), (interior, boundary));
+        }
+    }
+
+    #[test]
+    fn test_large_coordinates() {
+        let n = 1_000_000_000;
+        let triangle = points(&[(0, 0), (n, 0), (0, n)]);
+        let big = u128::try_from(n).unwrap();
+        assert_eq!(doubled_area(&triangle), i128::from(n) * i128::from(n));
+        assert_eq!(
+            lattice_points(&triangle),
+            ((big - 1) * (big - 2) / 2, 3 * big)
+        );
+        assert_eq!(locate(&triangle, Point::new(1, 1)), Location::Inside);
+        assert_eq!(
+            locate(&triangle, Point::new(n / 2, n / 2)),
+            Location::Boundary
+        );
+        assert_eq!(
+            locate(&triangle, Point::new(n / 2, n / 2 + 1)),
+            Location::Outside
+        );
+
+        // a comb with 10^5 teeth of width 2 and height 10^9, over a strip of height 1, clockwise
+        let teeth = 100_000;
+        let mut comb = vec![Point::new(0, 0)];
+        for tooth in 0..teeth {
+            let x = 4 * tooth;
+            comb.extend(points(&[(x, n), (x + 2, n), (x + 2, 1), (x + 4, 1)]));
+        }
+        comb.push(Point::new(4 * teeth, 0));
+        assert_eq!(
+            doubled_area(&comb),
+            -4 * i128::from(teeth) * i128::from(n + 1)
+        );
+        let last = 4 * teeth;
+        assert_eq!(locate(&comb, Point::new(last - 3, n - 1)), Location::Inside);
+        assert_eq!(locate(&comb, Point::new(last - 1, 2)), Location::Outside);
+        assert_eq!(locate(&comb, Point::new(last - 1, 1)), Location::Boundary);
+        assert_eq!(locate(&comb, Point::new(last, 1)), Location::Boundary);
+        assert_eq!(locate(&comb, Point::new(last + 1, 0)), Location::Outside);
+    }
+}
@@ -0,0 +1,255 @@
+//! Import CSES test case archives into `tests/data/<bin-name>/`, optionally generating a `#[test]` per case.
+//!
+//! The generated tests live in a marked block at the end of the bin's `mod test`, so re-importing replaces them.
+
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::validate;
+use crate::zip::Archive;
+
+const BEGIN_MARKER: &str = "    // CSES test data, generated by `cargo run --bin import` //\n";
+const END_MARKER: &str = "    // end of CSES test data //\n";
+
+/// Extract every `<name>.in`/`<name>.out` pair of the archive into `dir`, ignoring any directory structure.
+/// Returns the case names, sorted numerically.
+///
+/// # Errors
+///   raises `std::io::Error` if the archive is corrupt, a case is missing one of its files, or `dir` can't be written
+pub fn unpack(archive: &Archive, dir: &Path) -> io::Result<Vec<String>> {
+    let mut files = vec![];
+    for entry in archive.entries().iter().filter(|e| !e.is_dir()) {
+        if let Some((name, extension)) = entry.file_name().rsplit_once('.') {
+            if matches!(extension, "in" | "out") && !name.is_empty() {
+                files.push((name.to_string(), extension == "in", entry));
+            }
+        }
+    }
+
+    let mut names: Vec<String> = files.iter().map(|(name, ..)| name.clone()).collect();
+    names.sort_by_key(|name| (name.parse::<u64>().unwrap_or(u64::MAX), name.clone()));
+    names.dedup();
+    for name in &names {
+        let count = |input| {
+            files
+                .iter()
+                .filter(|f| &f.0 == name && f.1 == input)
+                .count()
+        };
+        if count(true) != 1 || count(false) != 1 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("case '{name}' needs exactly one .in and one .out file"),
+            ));
+        }
+    }
+
+    fs::create_dir_all(dir)?;
+    for (name, input, entry) in files {
+        let extension = if input { "in" } else { "out" };
+        fs::write(
+            dir.join(format!("{name}.{extension}")),
+            archive.read(entry)?,
+        )?;
+    }
+    Ok(names)
+}
+
+/// Insert (or replace) the generated tests for `cases` in the `mod test` of a bin's source.
+///
+/// # Errors
+///   returns a description of the problem if the source has no `solve` function
+pub fn generate_tests(source: &str, bin: &str, cases: &[String]) -> Result<String, String> {
+    let signature = source
+        .lines()
+        .find(|line| line.starts_with("fn solve"))
+        .ok_or_else(|| format!("{bin} has no solve function"))?;
+    // solve takes either a byte slice, a mutable buffer, a reader by value or a reader by reference
+    let argument = if signature.contains("&mut [u8]") || signature.contains("&mut Vec<u8>") {
+        "&mut input.to_vec()"
+    } else if signature.contains(": &mut R") {
+        "&mut { input }"
+    } else {
+        "input"
+    };
+
+    // the official data doubles as a check of the validator
+    let validate = if validate::validator(bin).is_some() {
+        format!("\n        rust_cses::validate::assert_valid(\"{bin}\", input);")
+    } else {
+        String::new()
+    };
+
+    let mut block = String::from(BEGIN_MARKER);
+    let _ = write!(
+        block,
+        "
+    /// compare token by token, CSES ignores whitespace differences
+    fn test_cses(input: &[u8], target: &[u8]) {{{validate}
+        let mut out = Vec::with_capacity(target.len());
+        solve({argument}, &mut out);
+
+        let mut expected = target.split(u8::is_ascii_whitespace).filter(|t| !t.is_empty());
+        let mut actual = out.split(u8::is_ascii_whitespace).filter(|t| !t.is_empty());
+        for token in 0.. {{
+            match (expected.next(), actual.next()) {{
+                (None, None) => break,
+                (e, a) => assert_eq!(
+                    a.map(String::from_utf8_lossy),
+                    e.map(String::from_utf8_lossy),
+                    \"token {{token}}\"
+                ),
+            }}
+        }}
+    }}
+"
+    );
+    for case in cases {
+        let test_name: String = case
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let _ = write!(
+            block,
+            "
+    #[test]
+    fn test_cses_{test_name}() {{
+        test_cses(
+            include_bytes!(\"../../tests/data/{bin}/{case}.in\"),
+            include_bytes!(\"../../tests/data/{bin}/{case}.out\"),
+        );
+    }}
+"
+        );
+    }
+    block.push_str(END_MARKER);
+
+    // drop a previous import
+    let mut source = source.to_string();
+    if let (Some(begin), Some(end)) = (source.find(BEGIN_MARKER), source.find(END_MARKER)) {
+        // also drop the blank line which separated the block from the hand-written tests
+        let begin = if source[..begin].ends_with("\n\n") {
+            begin - 1
+        } else {
+            begin
+        };
+        source.replace_range(begin..end + END_MARKER.len(), "");
+    }
+
+    match source.find("\nmod test {") {
+        Some(_) => {
+            let close = source.trim_end().len() - 1;
+            if source.as_bytes()[close] != b'}' {
+                return Err(format!("{bin} does not end with its test module"));
+            }
+            source.replace_range(close.., &format!("\n{block}}}\n"));
+        }
+        None => {
+            let _ = write!(
+                source,
+                "\n#[cfg(test)]\nmod test {{\n    use super::*;\n\n{block}}}\n"
+            );
+        }
+    }
+    Ok(source)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::zip::build_stored;
+
+    const SOURCE: &str = "\
+fn solve<W: std::io::Write>(scan: &[u8], out: &mut W) {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_example() {}
+}
+";
+
+    #[test]
+    fn test_unpack() {
+        let dir = std::env::temp_dir().join(format!("cses-import-test-{}", std::process::id()));
+        let data = build_stored(&[
+            ("tests/10.in", b"10\n"),
+            ("tests/10.out", b"20\n"),
+            ("tests/2.in", b"2\n"),
+            ("tests/2.out", b"4\n"),
+            ("README", b"ignored"),
+        ]);
+
+        let names = unpack(&Archive::new(&data).unwrap(), &dir).unwrap();
+        assert_eq!(names, ["2", "10"]);
+        assert_eq!(fs::read(dir.join("10.out")).unwrap(), b"20\n");
+        assert!(!dir.join("README").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_unpack_unpaired() {
+        let dir =
+            std::env::temp_dir().join(format!("cses-import-test-unpaired-{}", std::process::id()));
+        let data = build_stored(&[("1.in", b"1\n")]);
+        assert!(unpack(&Archive::new(&data).unwrap(), &dir).is_err());
+    }
+
+    #[test]
+    fn test_generate_tests() {
+        let generated =
+            generate_tests(SOURCE, "intro_example", &["1".to_string(), "2".to_string()]).unwrap();
+        assert!(generated.starts_with(SOURCE.strip_suffix("}\n").unwrap()));
+        assert!(generated.ends_with(&format!("    }}\n{END_MARKER}}}\n")));
+        assert!(generated.contains("solve(input, &mut out);"));
+        assert!(!generated.contains("assert_valid"));
+        assert!(generated.contains("fn test_cses_2() {"));
+        assert!(generated.contains("include_bytes!(\"../../tests/data/intro_example/1.out\")"));
+    }
+
+    #[test]
+    fn test_generate_tests_replaces_previous_import() {
+        let first =
+            generate_tests(SOURCE, "intro_example", &["1".to_string(), "2".to_string()]).unwrap();
+        let second = generate_tests(&first, "intro_example", &["3".to_string()]).unwrap();
+        assert_eq!(
+            second,
+            generate_tests(SOURCE, "intro_example", &["3".to_string()]).unwrap()
+        );
+        assert!(!second.contains("test_cses_1"));
+    }
+
+    #[test]
+    fn test_generate_tests_validates_input() {
+        let generated = generate_tests(SOURCE, "intro_weird_algorithm", &[]).unwrap();
+        assert!(generated.contains(
+            "fn test_cses(input: &[u8], target: &[u8]) {\n        rust_cses::validate::assert_valid(\"intro_weird_algorithm\", input);\n"
+        ));
+    }
+
+    #[test]
+    fn test_generate_tests_argument() {
+        let source = SOURCE.replace("scan: &[u8]", "scan: &mut Vec<u8>");
+        assert!(generate_tests(&source, "b", &[])
+            .unwrap()
+            .contains("solve(&mut input.to_vec(), &mut out);"));
+        let source = SOURCE.replace(
+            "<W: std::io::Write>(scan: &[u8]",
+            "<R: BufRead, W: Write>(read: &mut R",
+        );
+        assert!(generate_tests(&source, "b", &[])
+            .unwrap()
+            .contains("solve(&mut { input }, &mut out);"));
+        assert!(generate_tests("fn main() {}", "b", &[]).is_err());
+    }
+}
@@ -0,0 +1,336 @@
+//! Local judge: runs a solution against a folder of `N.in`/`N.out` pairs (the layout of the CSES test downloads).
+//!
+//! Every case is run as its own process, with stdin read from the `.in` file (like CSES does), a CPU-time limit and an
+//! address-space limit applied through `setrlimit`, and a wall-clock watchdog in case the process sleeps or blocks.
+//! Running processes is Linux only ([`process`]), building bins, finding cases and comparing outputs work everywhere.
+
+#[cfg(target_os = "linux")]
+pub mod process;
+
+#[cfg(target_os = "linux")]
+pub use process::{run, run_case};
+
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+/// Resource limits applied to every case, CSES uses 1 second and 512 MB for most problems.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limits {
+    pub time: Duration,
+    pub memory_bytes: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            time: Duration::from_secs(1),
+            memory_bytes: 512 << 20,
+        }
+    }
+}
+
+/// A single test case, `input` and `output` are sibling files named `<name>.in` and `<name>.out`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Case {
+    pub name: String,
+    pub input: PathBuf,
+    pub output: PathBuf,
+}
+
+/// First token where the expected and actual outputs disagree. Line numbers are 1-indexed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    /// 0-indexed position of the token in the whole output
+    pub token: usize,
+    pub expected_line: usize,
+    pub actual_line: usize,
+    /// `None` when the output ended before this token
+    pub expected: Option<String>,
+    pub actual: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Accepted,
+    WrongAnswer(Mismatch),
+    TimeLimitExceeded,
+    MemoryLimitExceeded,
+    RuntimeError(String),
+}
+
+impl Verdict {
+    /// short code as displayed by CSES
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Accepted => "AC",
+            Self::WrongAnswer(_) => "WA",
+            Self::TimeLimitExceeded => "TLE",
+            Self::MemoryLimitExceeded => "MLE",
+            Self::RuntimeError(_) => "RE",
+        }
+    }
+}
+
+/// Outcome of running one case.
+#[derive(Clone, Debug)]
+pub struct Execution {
+    pub verdict: Verdict,
+    pub cpu_time: Duration,
+    pub wall_time: Duration,
+    /// peak resident set size
+    pub memory_bytes: u64,
+    /// everything the process wrote to stdout
+    pub output: Vec<u8>,
+}
+
+/// Build `bin` with the release profile and return the path of the executable.
+///
+/// # Errors
+///   raises `std::io::Error` if cargo could not be spawned or the build failed
+pub fn build_release(bin: &str) -> io::Result<PathBuf> {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let status = Command::new(std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+        .args(["build", "--release", "--bin", bin])
+        .current_dir(manifest_dir)
+        .status()?;
+    if !status.success() {
+        return Err(io::Error::other(format!("failed to build bin '{bin}'")));
+    }
+
+    let target_dir = std::env::var_os("CARGO_TARGET_DIR")
+        .map_or_else(|| manifest_dir.join("target"), PathBuf::from);
+    Ok(target_dir.join("release").join(bin))
+}
+
+/// Collect every `N.in` in `dir` which has a matching `N.out`, sorted numerically by name (non-numeric names last).
+///
+/// # Errors
+///   raises `std::io::Error` if the directory cannot be read
+pub fn find_cases(dir: &Path) -> io::Result<Vec<Case>> {
+    let mut cases = vec![];
+    for entry in fs::read_dir(dir)? {
+        let input = entry?.path();
+        if input.extension() != Some(OsStr::new("in")) {
+            continue;
+        }
+        let output = input.with_extension("out");
+        if !output.is_file() {
+            continue;
+        }
+        let name = input
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned();
+        cases.push(Case {
+            name,
+            input,
+            output,
+        });
+    }
+
+    cases.sort_by(|a, b| {
+        let key = |case: &Case| {
+            (
+                case.name.parse::<u64>().unwrap_or(u64::MAX),
+                case.name.clone(),
+            )
+        };
+        key(a).cmp(&key(b))
+    });
+    Ok(cases)
+}
+
+/// Compare two outputs token by token, any amount of whitespace (including trailing whitespace) separates tokens.
+#[must_use]
+pub fn compare_tokens(expected: &[u8], actual: &[u8]) -> Option<Mismatch> {
+    let mut expected_tokens = tokens(expected);
+    let mut actual_tokens = tokens(actual);
+
+    for token in 0.. {
+        match (expected_tokens.next(), actual_tokens.next()) {
+            (None, None) => return None,
+            (e, a) if e.map(|(_, t)| t) == a.map(|(_, t)| t) => {}
+            (e, a) => {
+                let line_of = |token: Option<(usize, &[u8])>, buf: &[u8]| {
+                    line_at(buf, token.map_or(buf.len(), |(offset, _)| offset))
+                };
+                let text = |token: Option<(usize, &[u8])>| {
+                    token.map(|(_, t)| String::from_utf8_lossy(t).into_owned())
+                };
+                return Some(Mismatch {
+                    token,
+                    expected_line: line_of(e, expected),
+                    actual_line: line_of(a, actual),
+                    expected: text(e),
+                    actual: text(a),
+                });
+            }
+        }
+    }
+    unreachable!()
+}
+
+/// whitespace-separated tokens along with their byte offsets
+fn tokens(buf: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
+    buf.split(u8::is_ascii_whitespace)
+        .scan(0, |offset, token| {
+            let start = *offset;
+            *offset += token.len() + 1;
+            Some((start, token))
+        })
+        .filter(|(_, token)| !token.is_empty())
+}
+
+/// 1-indexed line containing the byte at `offset`
+fn line_at(buf: &[u8], offset: usize) -> usize {
+    buf[..offset].split(|&b| b == b'\n').count()
+}
+
+/// Render the mismatching lines of both outputs, long lines are cut down to a window around the mismatching token.
+#[must_use]
+pub fn render_diff(expected: &[u8], actual: &[u8], mismatch: &Mismatch) -> String {
+    /// tokens shown on each side of the mismatch
+    const CONTEXT: usize = 5;
+
+    let excerpt = |buf: &[u8], line: usize, token: &Option<String>| {
+        let Some(text) = buf.split(|&b| b == b'\n').nth(line - 1) else {
+            return "<end of output>".to_string();
+        };
+        let words: Vec<&[u8]> = text
+            .split(u8::is_ascii_whitespace)
+            .filter(|w| !w.is_empty())
+            .collect();
+        // position of the mismatching token within its line
+        let first_on_line = tokens(buf)
+            .take_while(|(offset, _)| line_at(buf, *offset) < line)
+            .count();
+        let center = if token.is_some() {
+            mismatch.token.saturating_sub(first_on_line)
+        } else {
+            words.len()
+        };
+        let (from, to) = (
+            center.saturating_sub(CONTEXT),
+            (center + CONTEXT + 1).min(words.len()),
+        );
+        let mut rendered = String::new();
+        if from > 0 {
+            rendered.push_str("... ");
+        }
+        for (idx, word) in words[from..to].iter().enumerate() {
+            let word = String::from_utf8_lossy(word);
+            if from + idx == center {
+                rendered.push('[');
+                rendered.push_str(&word);
+                rendered.push_str("] ");
+            } else {
+                rendered.push_str(&word);
+                rendered.push(' ');
+            }
+        }
+        if to < words.len() {
+            rendered.push_str("...");
+        }
+        if token.is_none() {
+            rendered.push_str("<end of output>");
+        }
+        rendered.trim_end().to_string()
+    };
+
+    format!(
+        "first difference at token {}\n  expected (line {}): {}\n  actual   (line {}): {}",
+        mismatch.token + 1,
+        mismatch.expected_line,
+        excerpt(expected, mismatch.expected_line, &mismatch.expected),
+        mismatch.actual_line,
+        excerpt(actual, mismatch.actual_line, &mismatch.actual),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// fresh scratch directory, unique per test
+    pub(super) fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("cses-judge-test-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    pub(super) fn case(dir: &Path, name: &str, input: &str, output: &str) -> Case {
+        let (input_path, output_path) = (
+            dir.join(format!("{name}.in")),
+            dir.join(format!("{name}.out")),
+        );
+        fs::write(&input_path, input).unwrap();
+        fs::write(&output_path, output).unwrap();
+        Case {
+            name: name.to_string(),
+            input: input_path,
+            output: output_path,
+        }
+    }
+
+    #[test]
+    fn test_compare_tokens_whitespace() {
+        assert_eq!(compare_tokens(b"2 5\n", b"2 5 "), None);
+        assert_eq!(compare_tokens(b"1\n2\n", b"1 2"), None);
+        assert_eq!(compare_tokens(b"", b"\n\n"), None);
+    }
+
+    #[test]
+    fn test_compare_tokens_mismatch() {
+        let mismatch = compare_tokens(b"1 2\n3 4\n", b"1 2\n3 5\n").unwrap();
+        assert_eq!(mismatch.token, 3);
+        assert_eq!((mismatch.expected_line, mismatch.actual_line), (2, 2));
+        assert_eq!(mismatch.expected.as_deref(), Some("4"));
+        assert_eq!(mismatch.actual.as_deref(), Some("5"));
+
+        let mismatch = compare_tokens(b"1 2 3\n", b"1 2\n").unwrap();
+        assert_eq!(mismatch.token, 2);
+        assert_eq!(mismatch.actual, None);
+        assert_eq!(mismatch.actual_line, 2);
+    }
+
+    #[test]
+    fn test_render_diff() {
+        let (expected, actual) = (
+            b"YES\n1 2 3 4 5 6 7 8 9 10\n",
+            b"YES\n1 2 3 4 5 6 7 9 8 10\n",
+        );
+        let mismatch = compare_tokens(expected, actual).unwrap();
+        assert_eq!(
+            render_diff(expected, actual, &mismatch),
+            "\
+first difference at token 9
+  expected (line 2): ... 3 4 5 6 7 [8] 9 10
+  actual   (line 2): ... 3 4 5 6 7 [9] 8 10"
+        );
+    }
+
+    #[test]
+    fn test_find_cases_numeric_order() {
+        let dir = scratch_dir("find");
+        for name in ["10", "2", "1"] {
+            case(&dir, name, "", "");
+        }
+        fs::write(dir.join("3.in"), "missing output").unwrap();
+
+        let names: Vec<String> = find_cases(&dir)
+            .unwrap()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(names, ["1", "2", "10"]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,287 @@
+//! Runs a solution as a child process under `setrlimit` limits, reaped by `wait4` for its resource usage.
+
+use super::{compare_tokens, Case, Execution, Limits, Verdict};
+
+use std::fs::{self, File};
+use std::io;
+use std::os::raw::{c_int, c_long};
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Run `executable` on one case and classify the result.
+///
+/// # Errors
+///   raises `std::io::Error` if the case files cannot be read or the process cannot be spawned;
+///   failures of the solution itself are reported through the verdict instead.
+pub fn run_case(executable: &Path, case: &Case, limits: Limits) -> io::Result<Execution> {
+    let expected = fs::read(&case.output)?;
+    let mut execution = run(executable, &case.input, limits)?;
+    if execution.verdict == Verdict::Accepted {
+        if let Some(mismatch) = compare_tokens(&expected, &execution.output) {
+            execution.verdict = Verdict::WrongAnswer(mismatch);
+        }
+    }
+    Ok(execution)
+}
+
+/// Run `executable` with stdin read from `input`, without checking the output:
+/// the verdict is `Accepted` whenever the process finished successfully within the limits.
+///
+/// # Errors
+///   raises `std::io::Error` if the input cannot be read or the process cannot be spawned
+pub fn run(executable: &Path, input: &Path, limits: Limits) -> io::Result<Execution> {
+    /// distinguishes the scratch files of concurrent runs
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    let run = RUNS.fetch_add(1, Ordering::Relaxed);
+    let scratch = std::env::temp_dir().join(format!("cses-judge-{}-{run}", std::process::id()));
+    let (stdout_path, stderr_path) = (
+        scratch.with_extension("stdout"),
+        scratch.with_extension("stderr"),
+    );
+
+    let cpu_seconds = limits.time.as_secs() + u64::from(limits.time.subsec_nanos() != 0);
+    let memory_bytes = limits.memory_bytes;
+    let mut command = Command::new(executable);
+    command
+        .stdin(Stdio::from(File::open(input)?))
+        .stdout(Stdio::from(File::create(&stdout_path)?))
+        .stderr(Stdio::from(File::create(&stderr_path)?));
+    // SAFETY: only async-signal-safe syscalls happen between fork and exec
+    unsafe {
+        command.pre_exec(move || {
+            sys::set_limit(sys::RLIMIT_CPU, cpu_seconds, cpu_seconds + 1)?;
+            sys::set_limit(sys::RLIMIT_AS, memory_bytes, memory_bytes)
+        });
+    }
+
+    let start = Instant::now();
+    let child = command.spawn()?;
+    let (status, usage) =
+        sys::wait_with_deadline(child, start + limits.time * 2 + Duration::from_secs(1))?;
+    let wall_time = start.elapsed();
+
+    let output = fs::read(&stdout_path)?;
+    let stderr = String::from_utf8_lossy(&fs::read(&stderr_path)?).into_owned();
+    let _ = fs::remove_file(&stdout_path);
+    let _ = fs::remove_file(&stderr_path);
+
+    let verdict = classify(status, &usage, &stderr, limits).unwrap_or(Verdict::Accepted);
+
+    Ok(Execution {
+        verdict,
+        cpu_time: usage.cpu_time,
+        wall_time,
+        memory_bytes: usage.max_rss_bytes,
+        output,
+    })
+}
+
+/// Verdict of a process which did not finish successfully within the limits, `None` if the output should be checked.
+fn classify(
+    status: Option<ExitStatus>,
+    usage: &sys::Usage,
+    stderr: &str,
+    limits: Limits,
+) -> Option<Verdict> {
+    const SIGXCPU: i32 = 24;
+
+    let Some(status) = status else {
+        // killed by the wall-clock watchdog
+        return Some(Verdict::TimeLimitExceeded);
+    };
+    if status.signal() == Some(SIGXCPU) || usage.cpu_time > limits.time {
+        return Some(Verdict::TimeLimitExceeded);
+    }
+    // exceeding RLIMIT_AS makes the allocator fail, which Rust reports on stderr before aborting
+    if usage.max_rss_bytes > limits.memory_bytes || stderr.contains("memory allocation of") {
+        return Some(Verdict::MemoryLimitExceeded);
+    }
+    if status.success() {
+        return None;
+    }
+
+    let reason = match (status.code(), status.signal()) {
+        (Some(code), _) => format!("exit code {code}"),
+        (_, Some(signal)) => format!("signal {signal}"),
+        _ => "unknown status".to_string(),
+    };
+    let last_line = stderr.lines().rev().find(|line| !line.trim().is_empty());
+    Some(Verdict::RuntimeError(match last_line {
+        Some(line) => format!("{reason}: {}", line.trim()),
+        None => reason,
+    }))
+}
+
+/// Minimal bindings for the few Linux syscalls std does not expose.
+mod sys {
+    use super::{c_int, c_long, io, Duration, ExitStatus, ExitStatusExt, Instant};
+    use std::process::Child;
+
+    pub const RLIMIT_CPU: c_int = 0;
+    pub const RLIMIT_AS: c_int = 9;
+    const WNOHANG: c_int = 1;
+
+    #[repr(C)]
+    struct RLimit {
+        current: u64,
+        max: u64,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct TimeVal {
+        seconds: c_long,
+        microseconds: c_long,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct RUsage {
+        user_time: TimeVal,
+        system_time: TimeVal,
+        max_rss_kilobytes: c_long,
+        rest: [c_long; 13],
+    }
+
+    extern "C" {
+        fn setrlimit(resource: c_int, limit: *const RLimit) -> c_int;
+        fn wait4(pid: c_int, status: *mut c_int, options: c_int, usage: *mut RUsage) -> c_int;
+    }
+
+    /// resources consumed by a finished child
+    pub struct Usage {
+        pub cpu_time: Duration,
+        pub max_rss_bytes: u64,
+    }
+
+    pub fn set_limit(resource: c_int, current: u64, max: u64) -> io::Result<()> {
+        if unsafe { setrlimit(resource, &RLimit { current, max }) } == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    /// Reap the child, killing it if it is still running at `deadline`.
+    /// The status is `None` if the child had to be killed.
+    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    pub fn wait_with_deadline(
+        mut child: Child,
+        deadline: Instant,
+    ) -> io::Result<(Option<ExitStatus>, Usage)> {
+        let pid = child.id() as c_int;
+        let mut status = 0;
+        let mut usage = RUsage::default();
+        let mut killed = false;
+        loop {
+            let options = if killed { 0 } else { WNOHANG };
+            match unsafe { wait4(pid, &mut status, options, &mut usage) } {
+                -1 => return Err(io::Error::last_os_error()),
+                0 if Instant::now() >= deadline => {
+                    child.kill()?;
+                    killed = true;
+                }
+                0 => std::thread::sleep(Duration::from_millis(1)),
+                _ => break,
+            }
+        }
+
+        let to_duration = |time: &TimeVal| {
+            Duration::from_secs(time.seconds as u64)
+                + Duration::from_micros(time.microseconds as u64)
+        };
+        let usage = Usage {
+            cpu_time: to_duration(&usage.user_time) + to_duration(&usage.system_time),
+            max_rss_bytes: usage.max_rss_kilobytes as u64 * 1024,
+        };
+        Ok(((!killed).then(|| ExitStatus::from_raw(status)), usage))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::judge::test::{case, scratch_dir};
+
+    use std::path::PathBuf;
+
+    /// write a shell script which acts as the solution
+    fn script(dir: &Path, body: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join("solution.sh");
+        fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_run_case_verdicts() {
+        let dir = scratch_dir("verdicts");
+        let solution = script(
+            &dir,
+            "read n; if [ \"$n\" = 3 ]; then exit 3; fi; echo $((n * 2)) ",
+        );
+        let limits = Limits::default();
+
+        let verdict = |name, input, output| {
+            run_case(&solution, &case(&dir, name, input, output), limits)
+                .unwrap()
+                .verdict
+        };
+        assert_eq!(verdict("1", "4\n", "8\n"), Verdict::Accepted);
+        assert_eq!(verdict("2", "4\n", "9\n").code(), "WA");
+        assert_eq!(
+            verdict("3", "3\n", "6\n"),
+            Verdict::RuntimeError("exit code 3".to_string())
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_run_case_time_limit() {
+        let dir = scratch_dir("tle");
+        let solution = script(&dir, "while :; do :; done");
+        let limits = Limits {
+            time: Duration::from_millis(200),
+            ..Limits::default()
+        };
+
+        let execution = run_case(&solution, &case(&dir, "1", "", ""), limits).unwrap();
+        assert_eq!(execution.verdict, Verdict::TimeLimitExceeded);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    /// run by `test_run_case_memory_limit`, under its limit
+    #[test]
+    #[ignore = "allocates 1 GB"]
+    fn allocate_past_limit() {
+        std::hint::black_box(vec![0_u8; 1 << 30]);
+    }
+
+    #[test]
+    fn test_run_case_memory_limit() {
+        let dir = scratch_dir("mle");
+        // this test binary, running the allocation above
+        let executable = std::env::current_exe().unwrap();
+        let solution = script(
+            &dir,
+            &format!(
+                "exec '{}' --ignored --exact judge::process::test::allocate_past_limit",
+                executable.display()
+            ),
+        );
+        let limits = Limits {
+            memory_bytes: 256 << 20,
+            ..Limits::default()
+        };
+
+        let execution = run_case(&solution, &case(&dir, "1", "", ""), limits).unwrap();
+        assert_eq!(execution.verdict, Verdict::MemoryLimitExceeded);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
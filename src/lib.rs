@@ -0,0 +1,20 @@
+//! Tooling used while developing solutions, shared by the helper binaries in `src/tools`.
+//!
+//! Nothing in `src/bin` depends on this library at runtime: CSES only accepts single-file submissions,
+//! so every solution keeps carrying its own I/O boilerplate.
+//! The other way around, [`solutions`] compiles the bins into the library so they can be run in-process.
+//!
+//! Reusable algorithms ([`geometry`], [`string`], [`tree`]) are dependency-free modules, tested here and copied into the bins that need them.
+
+pub mod geometry;
+pub mod import;
+pub mod judge;
+pub mod minimize;
+pub mod registry;
+pub mod scaffold;
+#[cfg(not(test))]
+pub mod solutions;
+pub mod string;
+pub mod tree;
+pub mod validate;
+pub mod zip;
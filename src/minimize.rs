@@ -0,0 +1,427 @@
+//! Shrink a failing input into a small one which still fails, by delta debugging over its structure.
+//!
+//! The input is seen as lines of space-separated tokens. Candidates drop chunks of lines (queries, edges, grid rows),
+//! chunks of tokens (array values, the same columns of parallel arrays), chunks of characters (strings, grid columns)
+//! and shrink integers towards zero. Dropping `k` items also tries lowering each integer of the first line by `k`, so
+//! counts like `n`/`m`/`q` follow. Whether a candidate is consistent is left to the problem's validator: only inputs
+//! which pass [`crate::validate::validate`] are given to the oracle.
+
+use std::fmt::Write;
+
+use crate::validate::{validate, validator};
+
+type Line = Vec<Vec<u8>>;
+
+fn parse(input: &[u8]) -> Vec<Line> {
+    let input = input.strip_suffix(b"\n").unwrap_or(input);
+    input
+        .split(|&b| b == b'\n')
+        .map(|line| {
+            if line.is_empty() {
+                vec![]
+            } else {
+                line.split(|&b| b == b' ').map(<[u8]>::to_vec).collect()
+            }
+        })
+        .collect()
+}
+
+fn render(lines: &[Line]) -> Vec<u8> {
+    let mut out = vec![];
+    for line in lines {
+        out.extend_from_slice(&line.join(&b' '));
+        out.push(b'\n');
+    }
+    out
+}
+
+fn integer(token: &[u8]) -> Option<i64> {
+    std::str::from_utf8(token).ok()?.parse().ok()
+}
+
+/// smaller candidates for an integer, closest to zero first
+fn smaller(value: i64) -> Vec<i64> {
+    let mut candidates = vec![0, value.signum(), value / 2, value - value.signum()];
+    candidates.retain(|candidate| candidate.abs() < value.abs());
+    candidates.dedup();
+    candidates
+}
+
+struct Shrinker<'a, F> {
+    bin: &'a str,
+    fails: F,
+    lines: Vec<Line>,
+    /// expected output lines, one per input line after the first
+    answers: Option<Vec<Vec<u8>>>,
+}
+
+impl<F: FnMut(&[u8], &[u8]) -> bool> Shrinker<'_, F> {
+    /// keep the candidate if it is valid and still fails
+    fn try_accept(&mut self, lines: Vec<Line>, answers: Option<Vec<Vec<u8>>>) -> bool {
+        let input = render(&lines);
+        if validate(self.bin, &input).is_err() {
+            return false;
+        }
+        let expected = answers.as_deref().map(render_answers).unwrap_or_default();
+        if (self.fails)(&input, &expected) {
+            self.lines = lines;
+            self.answers = answers;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// try `lines` as is, then with each integer of the first line lowered by `removed`
+    fn try_with_counts(
+        &mut self,
+        lines: &[Line],
+        removed: usize,
+        answers: Option<&[Vec<u8>]>,
+    ) -> bool {
+        let answers = answers.map(<[_]>::to_vec);
+        if self.try_accept(lines.to_vec(), answers.clone()) {
+            return true;
+        }
+        let removed = i64::try_from(removed).unwrap_or(i64::MAX);
+        for idx in 0..lines.first().map_or(0, Vec::len) {
+            let Some(count) = integer(&lines[0][idx]).filter(|&count| count >= removed) else {
+                continue;
+            };
+            let mut adjusted = lines.to_vec();
+            adjusted[0][idx] = (count - removed).to_string().into_bytes();
+            if self.try_accept(adjusted, answers.clone()) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// drop chunks of lines after the first one, halving the chunk size down to single lines
+    fn drop_lines(&mut self) {
+        let mut chunk = (self.lines.len() / 2).max(1);
+        loop {
+            let mut start = 1;
+            while start < self.lines.len() {
+                let end = (start + chunk).min(self.lines.len());
+                let mut lines = self.lines.clone();
+                lines.drain(start..end);
+                let answers = self.answers.clone().map(|mut answers| {
+                    answers.drain(start - 1..end - 1);
+                    answers
+                });
+                if !self.try_with_counts(&lines, end - start, answers.as_deref()) {
+                    start = end;
+                }
+            }
+            if chunk == 1 {
+                break;
+            }
+            chunk /= 2;
+        }
+    }
+
+    /// drop chunks of tokens from one line, or from every line with as many tokens (parallel arrays)
+    fn drop_tokens(&mut self) {
+        let mut row = 0;
+        while row < self.lines.len() {
+            let mut chunk = (self.lines[row].len() / 2).max(1);
+            loop {
+                let mut start = 0;
+                while start < self.lines[row].len() && self.lines[row].len() > 1 {
+                    let len = self.lines[row].len();
+                    let end = (start + chunk).min(len);
+                    let single = self.remove_tokens(&[row], start..end);
+                    let parallel: Vec<usize> = (1..self.lines.len())
+                        .filter(|&idx| self.lines[idx].len() == len)
+                        .collect();
+                    let accepted = self.try_with_counts(&single, end - start, None)
+                        || (parallel.len() > 1 && {
+                            let lines = self.remove_tokens(&parallel, start..end);
+                            self.try_with_counts(&lines, end - start, None)
+                        });
+                    if !accepted {
+                        start = end;
+                    }
+                }
+                if chunk == 1 {
+                    break;
+                }
+                chunk /= 2;
+            }
+            row += 1;
+        }
+    }
+
+    fn remove_tokens(&self, rows: &[usize], range: std::ops::Range<usize>) -> Vec<Line> {
+        let mut lines = self.lines.clone();
+        for &row in rows {
+            lines[row].drain(range.clone());
+        }
+        lines
+    }
+
+    /// drop chunks of characters from the words, alone or together with every word of the same length (grid columns)
+    fn drop_characters(&mut self) {
+        for row in 0..self.lines.len() {
+            for column in 0..self.lines[row].len() {
+                if integer(&self.lines[row][column]).is_some() {
+                    continue;
+                }
+                let mut chunk = (self.lines[row][column].len() / 2).max(1);
+                loop {
+                    let mut start = 0;
+                    while start < self.lines[row][column].len() && self.lines[row][column].len() > 1
+                    {
+                        let len = self.lines[row][column].len();
+                        let end = (start + chunk).min(len);
+                        let mut single = self.lines.clone();
+                        single[row][column].drain(start..end);
+                        let mut columns = self.lines.clone();
+                        for word in columns
+                            .iter_mut()
+                            .flatten()
+                            .filter(|word| word.len() == len)
+                        {
+                            word.drain(start..end);
+                        }
+                        let accepted = self.try_with_counts(&single, end - start, None)
+                            || self.try_with_counts(&columns, end - start, None);
+                        if !accepted {
+                            start = end;
+                        }
+                    }
+                    if chunk == 1 {
+                        break;
+                    }
+                    chunk /= 2;
+                }
+            }
+        }
+    }
+
+    /// move every integer towards zero
+    fn shrink_integers(&mut self) {
+        for row in 0..self.lines.len() {
+            for column in 0..self.lines[row].len() {
+                while let Some(value) = integer(&self.lines[row][column]) {
+                    let accepted = smaller(value).into_iter().any(|candidate| {
+                        let mut lines = self.lines.clone();
+                        lines[row][column] = candidate.to_string().into_bytes();
+                        self.try_accept(lines, self.answers.clone())
+                    });
+                    if !accepted {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn render_answers(answers: &[Vec<u8>]) -> Vec<u8> {
+    let mut out = answers.join(&b'\n');
+    out.push(b'\n');
+    out
+}
+
+fn check_start(bin: &str, input: &[u8]) -> Result<(), String> {
+    if validator(bin).is_none() {
+        return Err(format!(
+            "{bin} has no validator, candidates could not be checked"
+        ));
+    }
+    validate(bin, input)
+        .map_err(|violation| format!("the input is invalid for {bin} at {violation}"))
+}
+
+/// Shrink `input` while `fails` keeps returning true, trying candidates until none of them is accepted anymore.
+///
+/// # Errors
+///   returns a description of the problem if `bin` has no validator, the input is invalid, or it doesn't fail
+pub fn minimize(
+    bin: &str,
+    input: &[u8],
+    mut fails: impl FnMut(&[u8]) -> bool,
+) -> Result<Vec<u8>, String> {
+    check_start(bin, input)?;
+    if !fails(input) {
+        return Err("the input does not fail".to_string());
+    }
+
+    let mut shrinker = Shrinker {
+        bin,
+        fails: |input: &[u8], _: &[u8]| fails(input),
+        lines: parse(input),
+        answers: None,
+    };
+    loop {
+        let before = shrinker.lines.clone();
+        shrinker.drop_lines();
+        shrinker.drop_tokens();
+        shrinker.drop_characters();
+        shrinker.shrink_integers();
+        if shrinker.lines == before {
+            return Ok(render(&shrinker.lines));
+        }
+    }
+}
+
+/// Shrink an input whose expected output has one line per input line after the first (one answer per test case or
+/// query), dropping input lines together with their answers. Integers aren't shrunk, the answers would change.
+/// `fails` gets a candidate input and its expected output.
+///
+/// # Errors
+///   returns a description of the problem if `bin` has no validator, the input is invalid, the answers don't line up
+///   with the input lines, or the input doesn't fail
+pub fn minimize_queries(
+    bin: &str,
+    input: &[u8],
+    expected: &[u8],
+    fails: impl FnMut(&[u8], &[u8]) -> bool,
+) -> Result<(Vec<u8>, Vec<u8>), String> {
+    check_start(bin, input)?;
+    let lines = parse(input);
+    let answers: Vec<Vec<u8>> = expected
+        .split(|&b| b == b'\n')
+        .filter(|line| !line.iter().all(u8::is_ascii_whitespace))
+        .map(|line| {
+            let end = line
+                .iter()
+                .rposition(|b| !b.is_ascii_whitespace())
+                .map_or(0, |idx| idx + 1);
+            line[..end].to_vec()
+        })
+        .collect();
+    if answers.len() + 1 != lines.len() {
+        return Err(format!(
+            "the expected output has {} lines for {} input lines after the first one, it can't be shrunk along with the \
+             input (use a reference bin or a checker instead)",
+            answers.len(),
+            lines.len() - 1
+        ));
+    }
+
+    let mut shrinker = Shrinker {
+        bin,
+        fails,
+        lines,
+        answers: Some(answers),
+    };
+    if !(shrinker.fails)(
+        input,
+        &render_answers(shrinker.answers.as_deref().unwrap_or_default()),
+    ) {
+        return Err("the input does not fail".to_string());
+    }
+    shrinker.drop_lines();
+    let answers = render_answers(shrinker.answers.as_deref().unwrap_or_default());
+    Ok((render(&shrinker.lines), answers))
+}
+
+/// A `#[test]` for the bin's test module, in the style of the hand-written ones. `target_is_str` matches bins whose
+/// `test` helper takes the target as `&str`.
+#[must_use]
+pub fn format_test(name: &str, input: &[u8], target: &[u8], target_is_str: bool) -> String {
+    let prefix = if target_is_str { "" } else { "b" };
+    let mut test = String::new();
+    let _ = write!(
+        test,
+        "    #[test]
+    fn {name}() {{
+        let input = b\"\\
+{}\";
+        let target = {prefix}\"\\
+{}\";
+
+        test(input, target);
+    }}
+",
+        String::from_utf8_lossy(input),
+        String::from_utf8_lossy(target),
+    );
+    test
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_drop_queries_and_edges() {
+        // any edge touching node 7 breaks the pretend solution
+        let input = b"7 6\n1 2\n2 3\n3 7\n4 5\n5 6\n6 1\n";
+        let minimized = minimize("graph_building_roads", input, |input| {
+            parse(input)[1..]
+                .iter()
+                .flatten()
+                .any(|token| token == b"7")
+        })
+        .unwrap();
+        assert_eq!(minimized, b"7 1\n1 7\n");
+    }
+
+    #[test]
+    fn test_parallel_arrays() {
+        let input = b"4 10\n5 6 7 8\n1 2 3 100\n";
+        let minimized = minimize("dynamic_book_shop", input, |input| {
+            parse(input)[2]
+                .iter()
+                .any(|token| integer(token).unwrap() > 50)
+        })
+        .unwrap();
+        assert_eq!(minimized, b"1 1\n1\n51\n");
+    }
+
+    #[test]
+    fn test_grid_columns() {
+        let input = b"3 4\n..#.\n.##.\n....\n";
+        let minimized =
+            minimize("graph_counting_rooms", input, |input| input.contains(&b'#')).unwrap();
+        assert_eq!(minimized, b"1 1\n#\n");
+    }
+
+    #[test]
+    fn test_strings() {
+        let minimized = minimize("string_finding_borders", b"abcabxyzab\n", |input| {
+            input.windows(2).any(|pair| pair == b"ab")
+        })
+        .unwrap();
+        assert_eq!(minimized, b"ab\n");
+    }
+
+    #[test]
+    fn test_queries_with_answers() {
+        let input = b"4\n1 1\n2 3\n4 4\n5 2\n";
+        let expected = b"2\n2\n3\n3\n";
+        // the pretend solution is wrong whenever a query has y < x
+        let (input, expected) =
+            minimize_queries("intro_number_spiral", input, expected, |input, _| {
+                parse(input)[1..]
+                    .iter()
+                    .any(|line| integer(&line[0]) < integer(&line[1]))
+            })
+            .unwrap();
+        assert_eq!(input, b"1\n2 3\n");
+        assert_eq!(expected, b"2\n");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(minimize("0_cses_template", b"1 2\n", |_| false).is_err());
+        assert!(minimize("0_cses_template", b"1  2\n", |_| true).is_err());
+        assert!(minimize("interactive_hidden_integer_solution", b"1\n", |_| true).is_err());
+        assert!(
+            minimize_queries("intro_number_spiral", b"1\n2 3\n", b"8\n3\n", |_, _| true).is_err()
+        );
+    }
+
+    #[test]
+    fn test_format_test() {
+        assert_eq!(
+            format_test("test_minimized", b"1 2\n", b"3\n", false),
+            "    #[test]\n    fn test_minimized() {\n        let input = b\"\\\n1 2\n\";\n        let target = b\"\\\n3\n\";\n\n        test(input, target);\n    }\n"
+        );
+        assert!(format_test("t", b"", b"", true).contains("let target = \"\\\n"));
+    }
+}
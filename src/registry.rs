@@ -0,0 +1,379 @@
+//! Registry of the solutions in `src/bin`, matched against a bundled copy of the CSES problem list.
+//!
+//! A bin belongs to a problem through the `<CATEGORY>_<PROBLEM_NAME>` naming convention, its metadata comes from the
+//! doc comment of its `solve` function.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const CSES_PROBLEMS: &str = include_str!("registry/cses_problems.txt");
+
+/// A problem of the CSES problem set.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    /// category name, as shown on the website
+    pub category: &'static str,
+    /// condensed category, the prefix of the bin names
+    pub key: &'static str,
+    pub title: &'static str,
+    /// name of the bin solving it
+    pub bin: String,
+}
+
+/// The bundled problem list, in the order of the website.
+///
+/// # Panics
+///   if the bundled list is malformed
+#[must_use]
+pub fn problem_set() -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut category = None;
+    for line in CSES_PROBLEMS.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(header) = line.strip_prefix('[') {
+            let (key, name) = header
+                .split_once("] ")
+                .expect("category header is `[<key>] <name>`");
+            category = Some((key, name));
+            continue;
+        }
+
+        let (key, name) = category.expect("problem listed before any category");
+        let (title, bin) = match line.split_once(" = ") {
+            Some((title, name)) => (title, format!("{key}_{name}")),
+            None => (line, format!("{key}_{}", snake_case(line))),
+        };
+        problems.push(Problem {
+            category: name,
+            key,
+            title,
+            bin,
+        });
+    }
+    problems
+}
+
+/// `snake_case` version of a title: `Josephus Problem II` is `josephus_problem_2`, `Knight's Tour` is `knights_tour`.
+#[must_use]
+pub fn snake_case(title: &str) -> String {
+    let mut words: Vec<String> = title
+        .split(|c: char| c.is_whitespace() || c == '-')
+        .map(|word| {
+            word.chars()
+                .map(|c| match c {
+                    'ä' | 'å' => 'a',
+                    'ö' => 'o',
+                    'ü' => 'u',
+                    c => c.to_ascii_lowercase(),
+                })
+                .filter(char::is_ascii_alphanumeric)
+                .collect()
+        })
+        .filter(|word: &String| !word.is_empty())
+        .collect();
+    if let Some(last) = words
+        .last_mut()
+        .filter(|_| title.split_whitespace().count() > 1)
+    {
+        let numeral = match last.as_str() {
+            "i" => Some("1"),
+            "ii" => Some("2"),
+            "iii" => Some("3"),
+            _ => None,
+        };
+        if let Some(digit) = numeral {
+            *last = digit.to_string();
+        }
+    }
+    words.join("_")
+}
+
+/// The parts of a `solve` doc comment, the HTML markup copied from CSES is kept as is.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Statement {
+    /// everything before the first section
+    pub statement: String,
+    pub input: String,
+    pub output: String,
+    /// replaces input and output in interactive problems
+    pub interaction: String,
+    /// one entry per `<li>` of the constraints section
+    pub constraints: Vec<String>,
+}
+
+/// Parse the doc comment of the `solve` function in a bin's source.
+/// Returns `None` if there is no `solve` function or it isn't documented.
+#[must_use]
+pub fn parse_doc(source: &str) -> Option<Statement> {
+    let lines: Vec<&str> = source.lines().collect();
+    let solve = lines.iter().position(|line| line.starts_with("fn solve"))?;
+    // attributes may sit between the doc comment and the function
+    let start = lines[..solve]
+        .iter()
+        .rposition(|line| !line.starts_with("///") && !line.starts_with("#["))
+        .map_or(0, |idx| idx + 1);
+    let doc_lines: Vec<&str> = lines[start..solve]
+        .iter()
+        .filter_map(|line| line.strip_prefix("///"))
+        .collect();
+    if doc_lines.is_empty() {
+        return None;
+    }
+
+    let mut doc = Statement::default();
+    let mut section = String::new();
+    let mut text: Vec<&str> = vec![];
+    let flush = |section: &str, text: &mut Vec<&str>, doc: &mut Statement| {
+        let joined = text.join("\n").trim().to_string();
+        match section {
+            "" => doc.statement = joined,
+            "Input" => doc.input = joined,
+            "Output" => doc.output = joined,
+            "Interaction" => doc.interaction = joined,
+            "Constraints" => {
+                doc.constraints = text
+                    .iter()
+                    .filter_map(|line| line.trim().strip_prefix("<li>"))
+                    .map(|item| item.trim_end().trim_end_matches("</li>").trim().to_string())
+                    .collect();
+            }
+            _ => {}
+        }
+        text.clear();
+    };
+    for line in doc_lines {
+        let line = line.strip_prefix(' ').unwrap_or(line);
+        if let Some(heading) = line
+            .strip_prefix("<b>")
+            .and_then(|rest| rest.strip_suffix("</b>"))
+        {
+            flush(&section, &mut text, &mut doc);
+            section = heading.to_string();
+        } else {
+            text.push(line);
+        }
+    }
+    flush(&section, &mut text, &mut doc);
+    Some(doc)
+}
+
+/// A bin of `src/bin` solving a problem.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solution {
+    pub bin: String,
+    /// `None` if the bin matches no problem of the bundled list
+    pub problem: Option<Problem>,
+    pub doc: Statement,
+}
+
+impl Solution {
+    #[must_use]
+    pub fn category(&self) -> Option<&'static str> {
+        self.problem.as_ref().map(|problem| problem.category)
+    }
+
+    /// title of the problem, or the bin name if it matches no problem
+    #[must_use]
+    pub fn title(&self) -> &str {
+        self.problem
+            .as_ref()
+            .map_or(&self.bin, |problem| problem.title)
+    }
+}
+
+/// Every solution in `bin_dir`, sorted by bin name. The template and bins without a documented `solve` (like the
+/// interactor of an interactive problem) are skipped.
+///
+/// # Errors
+///   raises `std::io::Error` if the directory or a source file can't be read
+pub fn solutions(bin_dir: &Path, problems: &[Problem]) -> io::Result<Vec<Solution>> {
+    let mut solutions = vec![];
+    for entry in fs::read_dir(bin_dir)? {
+        let path = entry?.path();
+        let Some(bin) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        if path.extension().map_or(true, |extension| extension != "rs") || bin == "0_cses_template"
+        {
+            continue;
+        }
+        if let Some(doc) = parse_doc(&fs::read_to_string(&path)?) {
+            solutions.push(Solution {
+                bin: bin.to_string(),
+                problem: problems.iter().find(|problem| problem.bin == bin).cloned(),
+                doc,
+            });
+        }
+    }
+    solutions.sort_by(|a, b| a.bin.cmp(&b.bin));
+    Ok(solutions)
+}
+
+/// Solved problems of a category.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Coverage {
+    pub category: &'static str,
+    pub solved: usize,
+    pub total: usize,
+    /// unsolved problems, in the order of the website
+    pub missing: Vec<Problem>,
+}
+
+/// Per-category coverage of the problem set, categories in the order of the website.
+#[must_use]
+pub fn coverage(problems: &[Problem], solutions: &[Solution]) -> Vec<Coverage> {
+    let mut categories: Vec<Coverage> = vec![];
+    for problem in problems {
+        let solved = solutions.iter().any(|solution| solution.bin == problem.bin);
+        match categories.last_mut() {
+            Some(last) if last.category == problem.category => {
+                last.total += 1;
+                last.solved += usize::from(solved);
+                if !solved {
+                    last.missing.push(problem.clone());
+                }
+            }
+            _ => categories.push(Coverage {
+                category: problem.category,
+                solved: usize::from(solved),
+                total: 1,
+                missing: if solved {
+                    vec![]
+                } else {
+                    vec![problem.clone()]
+                },
+            }),
+        }
+    }
+    categories
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SOURCE: &str = "\
+// problem //
+
+/// Your task is to count.
+///
+/// <b>Input</b>
+///
+/// The only input line has an integer n.
+///
+/// <b>Output</b>
+///
+/// Print one integer.
+///
+/// <b>Constraints</b>
+///
+/// <ul>
+/// <li>1 ≤ n ≤ 10<sup>6</sup></li>
+/// <li>n is odd </li>
+/// </ul>
+#[allow(clippy::too_many_lines)]
+fn solve<W: std::io::Write>(scan: &[u8], out: &mut W) {}
+";
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("Josephus Problem II"), "josephus_problem_2");
+        assert_eq!(snake_case("Knight's Tour"), "knights_tour");
+        assert_eq!(snake_case("Fixed-Length Paths I"), "fixed_length_paths_1");
+        assert_eq!(snake_case("Prüfer Code"), "prufer_code");
+        assert_eq!(snake_case("Sum of Three Values"), "sum_of_three_values");
+        assert_eq!(snake_case("I"), "i");
+    }
+
+    #[test]
+    fn test_parse_doc() {
+        let doc = parse_doc(SOURCE).unwrap();
+        assert_eq!(doc.statement, "Your task is to count.");
+        assert_eq!(doc.input, "The only input line has an integer n.");
+        assert_eq!(doc.output, "Print one integer.");
+        assert_eq!(doc.constraints, ["1 ≤ n ≤ 10<sup>6</sup>", "n is odd"]);
+        assert_eq!(parse_doc("fn main() {}\nfn solve() {}\n"), None);
+    }
+
+    #[test]
+    fn test_problem_set() {
+        let problems = problem_set();
+        for (idx, problem) in problems.iter().enumerate() {
+            assert!(
+                problems[..idx].iter().all(|other| other.bin != problem.bin),
+                "{} is listed twice",
+                problem.bin
+            );
+        }
+        let bin = |title: &str| {
+            &problems
+                .iter()
+                .find(|problem| problem.title == title)
+                .unwrap()
+                .bin
+        };
+        assert_eq!(bin("Subarray Sums I"), "search_subarray_sums_1");
+        assert_eq!(bin("Sliding Window Median"), "sliding_window_median");
+        assert_eq!(bin("Grid Paths I"), "dynamic_grid_paths");
+
+        let tree = problems
+            .iter()
+            .filter(|problem| problem.category == "Tree Algorithms");
+        assert_eq!(tree.count(), 16);
+    }
+
+    #[test]
+    fn test_every_solution_is_listed() {
+        let problems = problem_set();
+        let bin_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/bin");
+        let solutions = solutions(&bin_dir, &problems).unwrap();
+        assert!(solutions
+            .iter()
+            .any(|solution| solution.bin == "tree_subordinates"));
+        for solution in &solutions {
+            assert!(
+                solution.problem.is_some(),
+                "{} is not in the problem list",
+                solution.bin
+            );
+            let doc = &solution.doc;
+            assert!(
+                !doc.input.is_empty() || !doc.interaction.is_empty(),
+                "{} has no input section",
+                solution.bin
+            );
+        }
+    }
+
+    #[test]
+    fn test_coverage() {
+        let problems = problem_set();
+        let solved = |bin: &str| Solution {
+            bin: bin.to_string(),
+            problem: problems.iter().find(|problem| problem.bin == bin).cloned(),
+            doc: Statement::default(),
+        };
+        let report = coverage(
+            &problems,
+            &[solved("tree_subordinates"), solved("intro_weird_algorithm")],
+        );
+        let tree = report
+            .iter()
+            .find(|category| category.category == "Tree Algorithms")
+            .unwrap();
+        assert_eq!((tree.solved, tree.total), (1, 16));
+        assert_eq!(tree.missing[0].title, "Tree Matching");
+        assert_eq!(
+            report.iter().map(|category| category.solved).sum::<usize>(),
+            2
+        );
+        assert_eq!(
+            report.iter().map(|category| category.total).sum::<usize>(),
+            problems.len()
+        );
+    }
+}
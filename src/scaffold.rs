@@ -0,0 +1,565 @@
+//! Create a new bin from `src/bin/0_cses_template.rs` and a problem statement saved as text.
+//!
+//! The statement file is the problem page copied from CSES, with the category and title on top:
+//!
+//! ```text
+//! Category: Tree Algorithms
+//! Title: Tree Diameter
+//!
+//! You are given a tree consisting of n nodes.
+//!
+//! Input
+//!
+//! The first input line contains an integer n: the number of nodes.
+//!
+//! Output
+//!
+//! Print one integer: the diameter of the tree.
+//!
+//! Constraints
+//!
+//! 1 ≤ n ≤ 2 * 10^5
+//!
+//! Example
+//!
+//! Input:
+//! 2
+//! 1 2
+//!
+//! Output:
+//! 1
+//! ```
+//!
+//! Section headings stand alone on their line, every line of the constraints is one constraint, and an example may
+//! be repeated. `10^5` and `x_i` become `10<sup>5</sup>` and `x<sub>i</sub>`, like in the existing doc comments.
+
+use std::fmt::Write;
+
+use crate::registry::{snake_case, Problem};
+
+/// How `main` reads stdin, `solve` gets the whole input as bytes either way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Entrypoint {
+    /// locked `std::io::stdin()`/`stdout()`, the usual choice
+    ReadToEnd,
+    /// the unbuffered `stdin_raw()`/`stdout_raw()` file descriptors from the template, saving a copy on large inputs
+    StdinRaw,
+}
+
+/// A problem statement read from a text file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Spec {
+    pub category: String,
+    pub title: String,
+    /// paragraphs before the first section
+    pub statement: Vec<String>,
+    pub input: Vec<String>,
+    pub output: Vec<String>,
+    /// used instead of input and output by interactive problems
+    pub interaction: Vec<String>,
+    pub constraints: Vec<String>,
+    /// (input, output) pairs, each ending with a newline
+    pub examples: Vec<(String, String)>,
+}
+
+/// Parse a statement file, see the module documentation for the format.
+///
+/// # Errors
+///   returns a description of the problem if the category, title, input, output or an example is missing
+pub fn parse_spec(text: &str) -> Result<Spec, String> {
+    let mut spec = Spec::default();
+    let mut section = "";
+    // paragraph being read, and the example input waiting for its output
+    let mut paragraph: Vec<&str> = vec![];
+    let mut example: Option<(&str, Vec<&str>)> = None;
+    let mut pending_input = None;
+
+    let mut lines = text.lines().map(str::trim_end);
+    for line in lines.by_ref() {
+        if let Some(category) = line.strip_prefix("Category:") {
+            spec.category = category.trim().to_string();
+        } else if let Some(title) = line.strip_prefix("Title:") {
+            spec.title = title.trim().to_string();
+        } else if !line.is_empty() {
+            paragraph.push(line);
+            break;
+        }
+    }
+
+    let finish_paragraph = |section: &str, paragraph: &mut Vec<&str>, spec: &mut Spec| {
+        if paragraph.is_empty() {
+            return;
+        }
+        let target = match section {
+            "Input" => &mut spec.input,
+            "Output" => &mut spec.output,
+            "Interaction" => &mut spec.interaction,
+            "Constraints" => {
+                spec.constraints
+                    .extend(paragraph.iter().map(|line| line.trim().to_string()));
+                paragraph.clear();
+                return;
+            }
+            _ => &mut spec.statement,
+        };
+        target.push(paragraph.join(" "));
+        paragraph.clear();
+    };
+    let finish_example = |example: &mut Option<(&str, Vec<&str>)>,
+                          pending_input: &mut Option<String>,
+                          spec: &mut Spec| {
+        if let Some((kind, lines)) = example.take() {
+            let mut data = lines.join("\n");
+            data.push('\n');
+            if kind == "Input:" {
+                *pending_input = Some(data);
+            } else if let Some(input) = pending_input.take() {
+                spec.examples.push((input, data));
+            }
+        }
+    };
+
+    for line in lines.chain(std::iter::once("")) {
+        if section == "Example" {
+            match line.trim() {
+                kind @ ("Input:" | "Output:") => {
+                    finish_example(&mut example, &mut pending_input, &mut spec);
+                    example = Some((kind, vec![]));
+                }
+                "" | "Example" | "Examples" => {
+                    finish_example(&mut example, &mut pending_input, &mut spec);
+                }
+                _ => match &mut example {
+                    Some((_, lines)) => lines.push(line),
+                    None => {
+                        return Err(format!(
+                            "example line '{line}' is not under Input: or Output:"
+                        ))
+                    }
+                },
+            }
+            continue;
+        }
+
+        match line.trim() {
+            heading @ ("Input" | "Output" | "Interaction" | "Constraints" | "Example"
+            | "Examples") => {
+                finish_paragraph(section, &mut paragraph, &mut spec);
+                section = if heading == "Examples" {
+                    "Example"
+                } else {
+                    heading
+                };
+            }
+            "" => finish_paragraph(section, &mut paragraph, &mut spec),
+            _ if line.starts_with("Time limit:") || line.starts_with("Memory limit:") => {}
+            _ => paragraph.push(line),
+        }
+    }
+
+    if spec.category.is_empty() || spec.title.is_empty() {
+        return Err(
+            "the file has to start with 'Category: ...' and 'Title: ...' lines".to_string(),
+        );
+    }
+    if spec.interaction.is_empty() && (spec.input.is_empty() || spec.output.is_empty()) {
+        return Err("the statement has no Input or Output section".to_string());
+    }
+    if spec.examples.is_empty() {
+        return Err(
+            "the statement has no example, expected 'Input:' and 'Output:' under 'Example'"
+                .to_string(),
+        );
+    }
+    Ok(spec)
+}
+
+/// Find the problem a spec is about, rejecting anything which would break the `<CATEGORY>_<PROBLEM_NAME>` convention.
+///
+/// # Errors
+///   returns a description of the problem if the category is unknown, the title isn't listed in that category, or
+///   the resulting bin name isn't lowercase `snake_case`
+pub fn resolve(spec: &Spec, problems: &[Problem]) -> Result<Problem, String> {
+    let category = problems
+        .iter()
+        .find(|problem| problem.category == spec.category || problem.key == spec.category)
+        .ok_or_else(|| format!("unknown category '{}'", spec.category))?;
+    let problem = problems
+        .iter()
+        .find(|problem| problem.key == category.key && snake_case(problem.title) == snake_case(&spec.title))
+        .ok_or_else(|| match problems.iter().find(|problem| problem.title == spec.title) {
+            Some(other) => format!("'{}' is in {}, not {}", spec.title, other.category, category.category),
+            None => format!(
+                "'{}' is not in the bundled problem list, add it to src/registry/cses_problems.txt first",
+                spec.title
+            ),
+        })?;
+
+    let valid = problem.bin.split('_').all(|word| {
+        !word.is_empty()
+            && word
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+    });
+    if !valid || !problem.bin.starts_with(&format!("{}_", problem.key)) {
+        return Err(format!(
+            "'{}' is not a <CATEGORY>_<PROBLEM_NAME> bin name",
+            problem.bin
+        ));
+    }
+    Ok(problem.clone())
+}
+
+/// `10^5` to `10<sup>5</sup>` and `x_i` to `x<sub>i</sub>`, the text is otherwise copied as is
+fn markup(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut previous = ' ';
+    while let Some(c) = chars.next() {
+        let tag = match c {
+            '^' => "sup",
+            '_' => "sub",
+            _ => "",
+        };
+        if !tag.is_empty()
+            && previous.is_alphanumeric()
+            && chars.peek().is_some_and(|c| c.is_alphanumeric())
+        {
+            let mut script = String::new();
+            while let Some(&c) = chars.peek().filter(|c| c.is_alphanumeric()) {
+                script.push(c);
+                chars.next();
+            }
+            let _ = write!(out, "<{tag}>{script}</{tag}>");
+            previous = 'x';
+        } else {
+            out.push(c);
+            previous = c;
+        }
+    }
+    out
+}
+
+/// `text` as the body of a `b"\` byte string literal, whose line continuation strips the leading whitespace: the
+/// lines are kept, but backslashes, quotes, non-ASCII bytes and that leading whitespace are escaped
+fn byte_string_body(text: &str) -> String {
+    let mut body = String::with_capacity(text.len());
+    for (idx, &byte) in text.as_bytes().iter().enumerate() {
+        match byte {
+            b'\\' => body.push_str("\\\\"),
+            b'"' => body.push_str("\\\""),
+            b' ' | b'\t' | b'\n' if idx == 0 => {
+                let _ = write!(body, "\\x{byte:02x}");
+            }
+            b'\n' | b'\t' | b' '..=b'~' => body.push(char::from(byte)),
+            _ => {
+                let _ = write!(body, "\\x{byte:02x}");
+            }
+        }
+    }
+    body
+}
+
+/// the `solve` doc comment, in the style of the existing bins
+fn doc_comment(spec: &Spec) -> String {
+    let mut doc = String::new();
+    let paragraphs = |doc: &mut String, paragraphs: &[String]| {
+        for paragraph in paragraphs {
+            let _ = writeln!(doc, "/// {}\n///", markup(paragraph));
+        }
+    };
+    paragraphs(&mut doc, &spec.statement);
+    let sections: [(&str, &[String]); 3] = [
+        ("Input", &spec.input),
+        ("Output", &spec.output),
+        ("Interaction", &spec.interaction),
+    ];
+    for (heading, text) in sections {
+        if !text.is_empty() {
+            let _ = writeln!(doc, "/// <b>{heading}</b>\n///");
+            paragraphs(&mut doc, text);
+        }
+    }
+    if spec.constraints.is_empty() {
+        // no trailing blank doc line
+        doc.truncate(doc.len() - "///\n".len());
+    } else {
+        doc.push_str("/// <b>Constraints</b>\n///\n/// <ul>\n");
+        for constraint in &spec.constraints {
+            let _ = writeln!(doc, "/// <li>{}</li>", markup(constraint));
+        }
+        doc.push_str("/// </ul>\n");
+    }
+    doc
+}
+
+/// Render the source of a new bin: the template's I/O boilerplate, the documented `solve` and its example tests.
+///
+/// # Errors
+///   returns a description of the problem if the template has no `// problem //` marker
+pub fn render(
+    spec: &Spec,
+    problem: &Problem,
+    template: &str,
+    entrypoint: Entrypoint,
+) -> Result<String, String> {
+    let (boilerplate, _) = template
+        .split_once("// problem //\n")
+        .ok_or_else(|| "the template has no '// problem //' marker".to_string())?;
+    let mut source = match entrypoint {
+        Entrypoint::StdinRaw => boilerplate.to_string(),
+        Entrypoint::ReadToEnd => {
+            // the raw file descriptors would be dead code
+            let raw_start = boilerplate
+                .find("// unbuffered stdin/stdout functions")
+                .unwrap_or(boilerplate.len());
+            boilerplate[..raw_start].replacen("use std::fs::File;\n", "", 1)
+        }
+    };
+    let (stdin, stdout) = match entrypoint {
+        Entrypoint::StdinRaw => ("stdin_raw()", "stdout_raw()"),
+        Entrypoint::ReadToEnd => ("std::io::stdin().lock()", "std::io::stdout().lock()"),
+    };
+
+    let _ = write!(
+        source,
+        "// problem //
+
+{doc}fn solve<W: std::io::Write>(scan: &[u8], out: &mut W) {{
+    let mut iter = scan.split(|n| *n <= b' ');
+    let mut writer = CustomBufWriter::new(out);
+
+    todo!(\"{title}\")
+}}
+
+// entrypoints //
+
+fn main() {{
+    let mut buf_str = vec![];
+    {stdin}.read_to_end(&mut buf_str).unwrap();
+    let mut out = {stdout};
+    solve(&buf_str, &mut out);
+}}
+
+#[cfg(test)]
+mod test {{
+    use super::*;
+
+    fn test(input: &[u8], target: &[u8]) {{
+        rust_cses::validate::assert_valid(\"{bin}\", input);
+        let mut out = Vec::with_capacity(target.len());
+        solve(input, &mut out);
+
+        assert_eq!(out, target);
+    }}
+",
+        doc = doc_comment(spec),
+        title = problem.title,
+        bin = problem.bin,
+    );
+    for (idx, (input, output)) in spec.examples.iter().enumerate() {
+        let name = if idx == 0 {
+            "test_example".to_string()
+        } else {
+            format!("test_example_{}", idx + 1)
+        };
+        let (input, output) = (byte_string_body(input), byte_string_body(output));
+        let _ = write!(
+            source,
+            "
+    #[test]
+    fn {name}() {{
+        let input = b\"\\
+{input}\";
+        let target = b\"\\
+{output}\";
+
+        test(input, target);
+    }}
+"
+        );
+    }
+    source.push_str("}\n");
+    Ok(source)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::registry::problem_set;
+
+    const SPEC: &str = "\
+Category: Tree Algorithms
+Title: Tree Diameter
+
+You are given a tree consisting of n nodes.
+The diameter of a tree is the maximum distance between two nodes.
+
+Input
+
+The first input line contains an integer n: the number of nodes. The nodes are numbered 1,2,...,n.
+
+Output
+
+Print one integer: the diameter of the tree.
+
+Constraints
+
+1 ≤ n ≤ 2 * 10^5
+1 ≤ a_i,b_i ≤ n
+
+Example
+
+Input:
+2
+1 2
+
+Output:
+1
+
+Example
+
+Input:
+1
+
+Output:
+0
+";
+
+    const TEMPLATE: &str = "\
+// I/O boilerplate //
+
+use std::fs::File;
+use std::io::prelude::*;
+
+// unbuffered stdin/stdout functions, see somewhere
+
+fn stdin_raw() -> File {}
+
+// problem //
+
+fn solve() {}
+";
+
+    #[test]
+    fn test_parse_spec() {
+        let spec = parse_spec(SPEC).unwrap();
+        assert_eq!(spec.category, "Tree Algorithms");
+        assert_eq!(spec.title, "Tree Diameter");
+        assert_eq!(
+            spec.statement,
+            ["You are given a tree consisting of n nodes. The diameter of a tree is the maximum distance between two nodes."]
+        );
+        assert_eq!(
+            spec.output,
+            ["Print one integer: the diameter of the tree."]
+        );
+        assert_eq!(spec.constraints, ["1 ≤ n ≤ 2 * 10^5", "1 ≤ a_i,b_i ≤ n"]);
+        assert_eq!(
+            spec.examples,
+            [
+                ("2\n1 2\n".to_string(), "1\n".to_string()),
+                ("1\n".to_string(), "0\n".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_spec_incomplete() {
+        assert!(parse_spec(&SPEC.replace("Title: Tree Diameter\n", "")).is_err());
+        assert!(parse_spec(&SPEC.replace("Output\n", "")).is_err());
+        assert!(parse_spec(SPEC.split("Example").next().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_markup() {
+        assert_eq!(markup("1 ≤ n ≤ 2 * 10^5"), "1 ≤ n ≤ 2 * 10<sup>5</sup>");
+        assert_eq!(
+            markup("1 ≤ a_i,b_i ≤ n"),
+            "1 ≤ a<sub>i</sub>,b<sub>i</sub> ≤ n"
+        );
+        assert_eq!(markup("print \"_\" or ^"), "print \"_\" or ^");
+    }
+
+    #[test]
+    fn test_resolve() {
+        let problems = problem_set();
+        let spec = parse_spec(SPEC).unwrap();
+        assert_eq!(resolve(&spec, &problems).unwrap().bin, "tree_tree_diameter");
+
+        let by_key = Spec {
+            category: "tree".to_string(),
+            ..spec.clone()
+        };
+        assert_eq!(
+            resolve(&by_key, &problems).unwrap().bin,
+            "tree_tree_diameter"
+        );
+
+        let wrong_category = Spec {
+            category: "Graph Algorithms".to_string(),
+            ..spec.clone()
+        };
+        assert_eq!(
+            resolve(&wrong_category, &problems).unwrap_err(),
+            "'Tree Diameter' is in Tree Algorithms, not Graph Algorithms"
+        );
+        let unknown = Spec {
+            category: "Trees".to_string(),
+            ..spec.clone()
+        };
+        assert!(resolve(&unknown, &problems).is_err());
+        let unlisted = Spec {
+            title: "Tree Painting".to_string(),
+            ..spec
+        };
+        assert!(resolve(&unlisted, &problems).is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let problems = problem_set();
+        let spec = parse_spec(SPEC).unwrap();
+        let problem = resolve(&spec, &problems).unwrap();
+
+        let source = render(&spec, &problem, TEMPLATE, Entrypoint::ReadToEnd).unwrap();
+        assert!(source
+            .starts_with("// I/O boilerplate //\n\nuse std::io::prelude::*;\n\n// problem //\n"));
+        assert!(source.contains(
+            "/// <b>Constraints</b>\n///\n/// <ul>\n/// <li>1 ≤ n ≤ 2 * 10<sup>5</sup></li>\n"
+        ));
+        assert!(
+            source.contains("/// </ul>\nfn solve<W: std::io::Write>(scan: &[u8], out: &mut W) {\n")
+        );
+        assert!(
+            source.contains("    std::io::stdin().lock().read_to_end(&mut buf_str).unwrap();\n")
+        );
+        assert!(
+            source.contains("rust_cses::validate::assert_valid(\"tree_tree_diameter\", input);")
+        );
+        assert!(
+            source.contains("    fn test_example() {\n        let input = b\"\\\n2\n1 2\n\";\n")
+        );
+        assert!(source.contains("    fn test_example_2() {"));
+        assert!(source.ends_with("        test(input, target);\n    }\n}\n"));
+
+        let source = render(&spec, &problem, TEMPLATE, Entrypoint::StdinRaw).unwrap();
+        assert!(source.contains("fn stdin_raw() -> File {}"));
+        assert!(source.contains("    stdin_raw().read_to_end(&mut buf_str).unwrap();\n"));
+        assert!(render(&spec, &problem, "fn main() {}", Entrypoint::StdinRaw).is_err());
+    }
+
+    #[test]
+    fn test_render_escaped_examples() {
+        let problems = problem_set();
+        let spec = Spec {
+            examples: vec![("  \"a\\b\"\n\tc\n".to_string(), "\n≤\n".to_string())],
+            ..parse_spec(SPEC).unwrap()
+        };
+        let problem = resolve(&spec, &problems).unwrap();
+
+        let source = render(&spec, &problem, TEMPLATE, Entrypoint::ReadToEnd).unwrap();
+        assert!(source.contains("        let input = b\"\\\n\\x20 \\\"a\\\\b\\\"\n\tc\n\";\n"));
+        assert!(source.contains("        let target = b\"\\\n\\x0a\\xe2\\x89\\xa4\n\";\n"));
+    }
+}
@@ -0,0 +1,144 @@
+//! Every solution of `src/bin` compiled into the library, callable in-process by name.
+//!
+//! The bins stay single files for submission: each one is pulled in with `include!` inside a module of its own, next
+//! to an adapter which runs its `solve` on an in-memory input. Interactive bins talk to an interactor and aren't listed.
+//!
+//! The solutions parse their input without any check, so [`solve_by_name`] validates it first. Only compiled outside of
+//! `cargo test` on the library, where the bins' own test modules would run a second time.
+
+/// Runs a solution on a whole input and returns everything it printed.
+pub type Solver = fn(&[u8]) -> Vec<u8>;
+
+/// `borrowed` bins take the input as `&[u8]` (or any `Read`), `copied` ones modify it and get their own copy
+macro_rules! solvers {
+    (borrowed: $($borrowed:ident),+ $(,)?; copied: $($copied:ident),+ $(,)?;) => {
+        $(solvers!(@module $borrowed, |input: &[u8], out: &mut Vec<u8>| solve(input, out));)+
+        $(solvers!(@module $copied, |input: &[u8], out: &mut Vec<u8>| solve(&mut input.to_vec(), out));)+
+
+        /// Find the solver of a bin.
+        #[must_use]
+        pub fn solver(bin: &str) -> Option<Solver> {
+            match bin {
+                $(stringify!($borrowed) => Some($borrowed::run),)+
+                $(stringify!($copied) => Some($copied::run),)+
+                _ => None,
+            }
+        }
+
+        /// Names of every bin which can be run in-process.
+        pub const SOLVED_BINS: &[&str] = &[$(stringify!($borrowed),)+ $(stringify!($copied),)+];
+    };
+    (@module $bin:ident, $call:expr) => {
+        #[allow(dead_code)]
+        mod $bin {
+            include!(concat!("bin/", stringify!($bin), ".rs"));
+
+            pub(super) fn run(input: &[u8]) -> Vec<u8> {
+                let mut out = vec![];
+                ($call)(input, &mut out);
+                out
+            }
+        }
+    };
+}
+
+solvers! {
+    borrowed:
+    additional_multiplication_table,
+    bitwise_counting_bits,
+    dynamic_array_description,
+    dynamic_book_shop,
+    dynamic_coin_combinations_1,
+    dynamic_coin_combinations_2,
+    dynamic_dice_combinations,
+    dynamic_edit_distance,
+    dynamic_grid_paths,
+    dynamic_increasing_subsequence,
+    dynamic_minimizing_coins,
+    dynamic_money_sums,
+    dynamic_removing_digits,
+    geometry_line_segment_intersection,
+    geometry_point_location_test,
+    graph_building_roads,
+    graph_building_teams,
+    graph_counting_rooms,
+    graph_labyrinth,
+    graph_message_route,
+    graph_round_trip,
+    graph_shortest_routes_1,
+    intro_apple_division,
+    intro_bit_strings,
+    intro_chessboard_and_queens,
+    intro_coin_piles,
+    intro_digit_queries,
+    intro_gray_code,
+    intro_grid_paths,
+    intro_increasing_array,
+    intro_missing_number,
+    intro_number_spiral,
+    intro_permutations,
+    intro_repetitions,
+    intro_tower_of_hanoi,
+    intro_trailing_zeros,
+    intro_two_knights,
+    intro_two_sets,
+    intro_weird_algorithm,
+    mathematics_counting_divisors,
+    mathematics_exponentiation,
+    mathematics_exponentiation_2,
+    mathematics_fibonacci_numbers,
+    mathematics_josephus_queries,
+    mathematics_prime_multiples,
+    mathematics_throwing_dice,
+    range_forest_queries,
+    range_range_xor_queries,
+    range_static_range_sum_queries,
+    search_apartments,
+    search_array_division,
+    search_collecting_numbers,
+    search_collecting_numbers_2,
+    search_concert_tickets,
+    search_distinct_numbers,
+    search_factory_machines,
+    search_ferris_wheel,
+    search_josephus_problem_1,
+    search_josephus_problem_2,
+    search_maximum_subarray_sum,
+    search_missing_coin_sum,
+    search_movie_festival,
+    search_nearest_smaller_values,
+    search_playlist,
+    search_reading_books,
+    search_restaurant_customers,
+    search_room_allocation,
+    search_stick_lengths,
+    search_subarray_divisibility,
+    search_subarray_sums_1,
+    search_subarray_sums_2,
+    search_sum_of_three_values,
+    search_sum_of_two_values,
+    search_tasks_and_deadlines,
+    search_towers,
+    search_traffic_lights,
+    sliding_window_median,
+    string_finding_borders,
+    string_string_matching,
+    tree_subordinates,
+    ;
+    copied:
+    intro_creating_strings,
+    intro_palindrome_reorder,
+    string_minimal_rotation,
+    ;
+}
+
+/// Run the solution of `bin` on `input` and return its output.
+///
+/// # Panics
+///   if `bin` is unknown or interactive, or `input` is invalid for it
+#[must_use]
+pub fn solve_by_name(bin: &str, input: &[u8]) -> Vec<u8> {
+    let solver = solver(bin).unwrap_or_else(|| panic!("no solution named {bin}"));
+    crate::validate::assert_valid(bin, input);
+    solver(input)
+}
@@ -0,0 +1,301 @@
+//! String algorithms on bytes, the starting point of the string problems.
+//!
+//! The prefix function (KMP), the Z-function and Duval's Lyndon factorization, which `string_string_matching`,
+//! `string_finding_borders` and `string_minimal_rotation` inline.
+
+pub mod aho_corasick;
+pub mod hash;
+pub mod palindrome;
+pub mod stream;
+pub mod suffix_array;
+pub mod suffix_automaton;
+pub mod trie;
+
+use std::ops::Range;
+
+/// `pi[i]` is the length of the longest proper border (prefix that is also a suffix) of `s[..=i]`.
+#[must_use]
+pub fn prefix_function(s: &[u8]) -> Vec<usize> {
+    let mut pi = vec![0; s.len()];
+    let mut len = 0;
+    for (idx, &ch) in s.iter().enumerate().skip(1) {
+        while len > 0 && s[len] != ch {
+            len = pi[len - 1];
+        }
+        if s[len] == ch {
+            len += 1;
+        }
+        pi[idx] = len;
+    }
+    pi
+}
+
+/// `z[i]` is the length of the longest common prefix of `s` and `s[i..]`, with `z[0] = s.len()`.
+#[must_use]
+pub fn z_function(s: &[u8]) -> Vec<usize> {
+    let n = s.len();
+    let mut z = vec![n; n];
+    // s[from..to] matches a prefix of s, with the largest `to` seen so far
+    let (mut from, mut to) = (0, 0);
+    for i in 1..n {
+        let mut len = if i < to { z[i - from].min(to - i) } else { 0 };
+        while i + len < n && s[len] == s[i + len] {
+            len += 1;
+        }
+        z[i] = len;
+        if i + len > to {
+            (from, to) = (i, i + len);
+        }
+    }
+    z
+}
+
+/// Lengths of the borders of `s` in increasing order, `s` itself excluded (Finding Borders).
+#[must_use]
+pub fn borders(s: &[u8]) -> Vec<usize> {
+    let pi = prefix_function(s);
+    let mut borders = vec![];
+    let mut len = pi.last().copied().unwrap_or_default();
+    while len > 0 {
+        borders.push(len);
+        len = pi[len - 1];
+    }
+    borders.reverse();
+    borders
+}
+
+/// Periods `p` of `s` in increasing order, `s[i] = s[i + p]` wherever both exist (Finding Periods).
+/// `s.len()` is always the last one.
+#[must_use]
+pub fn periods(s: &[u8]) -> Vec<usize> {
+    let n = s.len();
+    let z = z_function(s);
+    (1..=n).filter(|&p| p == n || z[p] == n - p).collect()
+}
+
+/// Start positions of the occurrences of a pattern in a text, overlapping ones included, from the prefix function.
+#[derive(Clone, Debug)]
+pub struct Matches<'a> {
+    text: &'a [u8],
+    pattern: &'a [u8],
+    pi: Vec<usize>,
+    /// next position of the text to read
+    position: usize,
+    /// length of the pattern prefix ending at `position`
+    len: usize,
+}
+
+impl Iterator for Matches<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while let Some(&ch) = self.text.get(self.position) {
+            self.position += 1;
+            if self.len == self.pattern.len() {
+                self.len = self.pi[self.len - 1];
+            }
+            while self.len > 0 && self.pattern[self.len] != ch {
+                self.len = self.pi[self.len - 1];
+            }
+            if self.pattern[self.len] == ch {
+                self.len += 1;
+            }
+            if self.len == self.pattern.len() {
+                return Some(self.position - self.len);
+            }
+        }
+        None
+    }
+}
+
+/// Occurrences of `pattern` in `text` (String Matching, Pattern Positions), in O(|text| + |pattern|).
+///
+/// # Panics
+///   if `pattern` is empty
+#[must_use]
+pub fn matches<'a>(text: &'a [u8], pattern: &'a [u8]) -> Matches<'a> {
+    assert!(!pattern.is_empty(), "empty pattern");
+    Matches {
+        text,
+        pattern,
+        pi: prefix_function(pattern),
+        position: 0,
+        len: 0,
+    }
+}
+
+/// Occurrences of `pattern` in `text` from the Z-function of `pattern` + `text`, the same positions as [`matches`].
+/// No separator is needed: an occurrence is any `z[m + i] >= m`, and a Z box can't run past the end of the text.
+///
+/// # Panics
+///   if `pattern` is empty
+pub fn z_matches(text: &[u8], pattern: &[u8]) -> impl Iterator<Item = usize> {
+    assert!(!pattern.is_empty(), "empty pattern");
+    let m = pattern.len();
+    let z = z_function(&[pattern, text].concat());
+    (0..text.len()).filter(move |&i| z[m + i] >= m)
+}
+
+/// Duval's algorithm: `s` as a non-increasing sequence of Lyndon words (strictly smaller than all their proper
+/// rotations), returned as consecutive ranges covering `s`.
+#[must_use]
+pub fn lyndon_factorization(s: &[u8]) -> Vec<Range<usize>> {
+    let n = s.len();
+    let mut factors = vec![];
+    let mut start = 0;
+    while start < n {
+        // s[start..end] is a power of a Lyndon word of length end - current, followed by a prefix of it
+        let (mut current, mut end) = (start, start + 1);
+        while end < n && s[current] <= s[end] {
+            current = if s[current] < s[end] {
+                start
+            } else {
+                current + 1
+            };
+            end += 1;
+        }
+        let len = end - current;
+        while start <= current {
+            factors.push(start..start + len);
+            start += len;
+        }
+    }
+    factors
+}
+
+/// Smallest `i` such that `s[i..] + s[..i]` is the lexicographically minimal rotation (Minimal Rotation).
+#[must_use]
+pub fn minimal_rotation_index(s: &[u8]) -> usize {
+    let n = s.len();
+    let doubled = [s, s].concat();
+    // the minimal rotation starts at the last Lyndon factor of s + s beginning in the first half
+    let start = lyndon_factorization(&doubled)
+        .into_iter()
+        .map(|factor| factor.start)
+        .take_while(|&start| start < n)
+        .last()
+        .unwrap_or_default();
+    // every rotation equal to it is a multiple of the smallest period dividing n away
+    let period = periods(s).into_iter().find(|&p| n % p == 0).unwrap_or(1);
+    start % period
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::ops::RangeInclusive;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// random string over the first `alphabet` lowercase letters, with a length in `lengths`
+    pub(crate) fn random_string(
+        rng: &mut StdRng,
+        alphabet: u8,
+        lengths: RangeInclusive<usize>,
+    ) -> Vec<u8> {
+        (0..rng.gen_range(lengths))
+            .map(|_| b'a' + rng.gen_range(0..alphabet))
+            .collect()
+    }
+
+    fn naive_prefix_function(s: &[u8]) -> Vec<usize> {
+        (0..s.len())
+            .map(|i| {
+                (0..=i)
+                    .rev()
+                    .find(|&len| s[..len] == s[i + 1 - len..=i] && len <= i)
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    fn naive_z_function(s: &[u8]) -> Vec<usize> {
+        (0..s.len())
+            .map(|i| s.iter().zip(&s[i..]).take_while(|(a, b)| a == b).count())
+            .collect()
+    }
+
+    fn is_lyndon(word: &[u8]) -> bool {
+        !word.is_empty() && (1..word.len()).all(|i| *word < [&word[i..], &word[..i]].concat()[..])
+    }
+
+    #[test]
+    fn test_examples() {
+        assert_eq!(prefix_function(b"abacaba"), [0, 0, 1, 0, 1, 2, 3]);
+        assert_eq!(z_function(b"aaabaab"), [7, 2, 1, 0, 2, 1, 0]);
+        assert_eq!(
+            matches(b"saippuakauppias", b"pp").collect::<Vec<_>>(),
+            [3, 10]
+        );
+        assert_eq!(matches(b"zzzzzz", b"zzz").count(), 4);
+        assert_eq!(borders(b"abcababcab"), [2, 5]);
+        assert_eq!(periods(b"abcabca"), [3, 6, 7]);
+        assert_eq!(lyndon_factorization(b"ababbaab"), [0..5, 5..8]);
+        assert_eq!(minimal_rotation_index(b"acab"), 2);
+        assert_eq!(minimal_rotation_index(b"abab"), 0);
+        assert_eq!(minimal_rotation_index(b""), 0);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..3000 {
+            let alphabet = rng.gen_range(1..=3);
+            let s = random_string(&mut rng, alphabet, 0..=30);
+            let n = s.len();
+            let pi = prefix_function(&s);
+            assert_eq!(pi, naive_prefix_function(&s), "{s:?}");
+            assert_eq!(z_function(&s), naive_z_function(&s), "{s:?}");
+
+            let naive_periods: Vec<usize> = (1..=n)
+                .filter(|&p| (p..n).all(|i| s[i] == s[i - p]))
+                .collect();
+            assert_eq!(periods(&s), naive_periods);
+            let naive_borders: Vec<usize> =
+                naive_periods.iter().rev().skip(1).map(|&p| n - p).collect();
+            assert_eq!(borders(&s), naive_borders);
+
+            let factors = lyndon_factorization(&s);
+            let mut end = 0;
+            for factor in &factors {
+                assert_eq!(factor.start, end);
+                assert!(is_lyndon(&s[factor.clone()]), "{s:?} {factors:?}");
+                end = factor.end;
+            }
+            assert_eq!(end, n);
+            assert!(factors
+                .windows(2)
+                .all(|w| s[w[0].clone()] >= s[w[1].clone()]));
+
+            let rotation = |i: usize| [&s[i..], &s[..i]].concat();
+            let minimal = (0..n).min_by_key(|&i| rotation(i)).unwrap_or_default();
+            assert_eq!(minimal_rotation_index(&s), minimal, "{s:?}");
+
+            let pattern = random_string(&mut rng, alphabet, 0..=4);
+            if !pattern.is_empty() {
+                let naive: Vec<usize> = (0..n).filter(|&i| s[i..].starts_with(&pattern)).collect();
+                assert_eq!(matches(&s, &pattern).collect::<Vec<_>>(), naive);
+                assert_eq!(z_matches(&s, &pattern).collect::<Vec<_>>(), naive);
+            }
+        }
+    }
+
+    #[test]
+    fn test_long_strings() {
+        let n = 1_000_000;
+        let s = vec![b'z'; n];
+        assert_eq!(matches(&s, b"zzz").count(), n - 2);
+        assert_eq!(periods(&s).len(), n);
+        assert_eq!(lyndon_factorization(&s).len(), n);
+        assert_eq!(minimal_rotation_index(&s), 0);
+
+        let mut rng = StdRng::seed_from_u64(2);
+        let s: Vec<u8> = (0..n).map(|_| b'a' + rng.gen_range(0..2)).collect();
+        let pattern = &s[n / 2..n / 2 + 10];
+        assert_eq!(
+            matches(&s, pattern).collect::<Vec<_>>(),
+            z_matches(&s, pattern).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! Aho–Corasick automaton over bytes, for Finding Patterns, Counting Patterns and Pattern Positions.
+//!
+//! A trie of the patterns with suffix links (the longest proper suffix of a node that is also in the trie) and
+//! dictionary links (the longest one that ends a pattern). Per-pattern answers come from counting the visits of every
+//! node while reading the text and summing them up the suffix-link tree, so they don't depend on the number of
+//! matches. Edges are kept as short lists, which suits the small alphabets of the problems and keeps the memory linear.
+
+/// No dictionary link.
+const NONE: usize = usize::MAX;
+const ROOT: usize = 0;
+
+#[derive(Clone, Debug)]
+pub struct AhoCorasick {
+    /// outgoing trie edges of every node
+    edges: Vec<Vec<(u8, usize)>>,
+    /// suffix link, the root for the root
+    fail: Vec<usize>,
+    /// nearest node on the suffix-link chain (itself excluded) ending a pattern, or `NONE`
+    dictionary: Vec<usize>,
+    /// patterns ending at every node, several for duplicate patterns
+    outputs: Vec<Vec<usize>>,
+    /// node and length of every pattern
+    pattern_node: Vec<usize>,
+    pattern_len: Vec<usize>,
+    /// nodes by increasing depth, so suffix links point backwards
+    bfs: Vec<usize>,
+}
+
+impl AhoCorasick {
+    /// # Panics
+    ///   if a pattern is empty
+    #[must_use]
+    pub fn new<P: AsRef<[u8]>>(patterns: &[P]) -> Self {
+        let mut edges: Vec<Vec<(u8, usize)>> = vec![vec![]];
+        let mut outputs = vec![vec![]];
+        let mut pattern_node = Vec::with_capacity(patterns.len());
+        for (idx, pattern) in patterns.iter().enumerate() {
+            let pattern = pattern.as_ref();
+            assert!(!pattern.is_empty(), "empty pattern");
+            let mut node = ROOT;
+            for &byte in pattern {
+                node = if let Some(&(_, next)) = edges[node].iter().find(|&&(b, _)| b == byte) {
+                    next
+                } else {
+                    let next = edges.len();
+                    edges.push(vec![]);
+                    outputs.push(vec![]);
+                    edges[node].push((byte, next));
+                    next
+                };
+            }
+            outputs[node].push(idx);
+            pattern_node.push(node);
+        }
+
+        let mut automaton = Self {
+            fail: vec![ROOT; edges.len()],
+            dictionary: vec![NONE; edges.len()],
+            bfs: Vec::with_capacity(edges.len()),
+            pattern_len: patterns.iter().map(|p| p.as_ref().len()).collect(),
+            edges,
+            outputs,
+            pattern_node,
+        };
+        automaton.bfs.push(ROOT);
+        let mut head = 0;
+        while let Some(&v) = automaton.bfs.get(head) {
+            head += 1;
+            for idx in 0..automaton.edges[v].len() {
+                let (byte, child) = automaton.edges[v][idx];
+                if v != ROOT {
+                    let fail = automaton.step(automaton.fail[v], byte);
+                    automaton.fail[child] = fail;
+                    automaton.dictionary[child] = if automaton.outputs[fail].is_empty() {
+                        automaton.dictionary[fail]
+                    } else {
+                        fail
+                    };
+                }
+                automaton.bfs.push(child);
+            }
+        }
+        automaton
+    }
+
+    /// node reached from `state` by reading `byte`, following suffix links while there is no edge
+    fn step(&self, mut state: usize, byte: u8) -> usize {
+        loop {
+            if let Some(&(_, next)) = self.edges[state].iter().find(|&&(b, _)| b == byte) {
+                return next;
+            }
+            if state == ROOT {
+                return ROOT;
+            }
+            state = self.fail[state];
+        }
+    }
+
+    /// number of trie nodes, the root included
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// there are no patterns, only the root
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 1
+    }
+
+    /// Every occurrence as `(pattern, start)`, ordered by end position, in O(|text| + output).
+    pub fn matches<'a>(&'a self, text: &'a [u8]) -> impl Iterator<Item = (usize, usize)> + 'a {
+        text.iter()
+            .enumerate()
+            .scan(ROOT, move |state, (idx, &byte)| {
+                *state = self.step(*state, byte);
+                Some((idx + 1, *state))
+            })
+            .flat_map(move |(end, state)| {
+                let first = if self.outputs[state].is_empty() {
+                    self.dictionary[state]
+                } else {
+                    state
+                };
+                std::iter::successors(Some(first).filter(|&v| v != NONE), move |&v| {
+                    Some(self.dictionary[v]).filter(|&v| v != NONE)
+                })
+                .flat_map(move |v| &self.outputs[v])
+                .map(move |&pattern| (pattern, end - self.pattern_len[pattern]))
+            })
+    }
+
+    /// `(visits, first end)` of every node and its suffix-link subtree, the end being 1 past the last byte read
+    fn visits(&self, text: &[u8]) -> (Vec<u64>, Vec<usize>) {
+        let mut visits = vec![0; self.len()];
+        let mut first_end = vec![usize::MAX; self.len()];
+        let mut state = ROOT;
+        for (idx, &byte) in text.iter().enumerate() {
+            state = self.step(state, byte);
+            visits[state] += 1;
+            first_end[state] = first_end[state].min(idx + 1);
+        }
+        // every visit of a node is a visit of its suffixes too
+        for &v in self.bfs[1..].iter().rev() {
+            let fail = self.fail[v];
+            visits[fail] += visits[v];
+            first_end[fail] = first_end[fail].min(first_end[v]);
+        }
+        (visits, first_end)
+    }
+
+    /// Counting Patterns: the number of occurrences of every pattern, in O(|text| + patterns).
+    #[must_use]
+    pub fn counts(&self, text: &[u8]) -> Vec<u64> {
+        let (visits, _) = self.visits(text);
+        self.pattern_node.iter().map(|&v| visits[v]).collect()
+    }
+
+    /// Finding Patterns: whether every pattern occurs.
+    #[must_use]
+    pub fn occurs(&self, text: &[u8]) -> Vec<bool> {
+        self.counts(text)
+            .into_iter()
+            .map(|count| count > 0)
+            .collect()
+    }
+
+    /// Pattern Positions: the start of the first occurrence of every pattern.
+    #[must_use]
+    pub fn first_positions(&self, text: &[u8]) -> Vec<Option<usize>> {
+        let (_, first_end) = self.visits(text);
+        self.pattern_node
+            .iter()
+            .zip(&self.pattern_len)
+            .map(|(&v, &len)| {
+                Some(first_end[v])
+                    .filter(|&end| end != usize::MAX)
+                    .map(|end| end - len)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::string::test::random_string;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_examples() {
+        let automaton = AhoCorasick::new(&["bab", "abc", "ayba"]);
+        let text = b"aybabtu";
+        assert_eq!(automaton.occurs(text), [true, false, true]);
+        assert_eq!(automaton.counts(text), [1, 0, 1]);
+        assert_eq!(automaton.first_positions(text), [Some(2), None, Some(0)]);
+        assert_eq!(
+            automaton.matches(text).collect::<Vec<_>>(),
+            [(2, 0), (0, 2)]
+        );
+
+        let automaton = AhoCorasick::new(&["a", "aa", "aa", "ba"]);
+        assert_eq!(automaton.len(), 5);
+        assert_eq!(automaton.counts(b"baaa"), [3, 2, 2, 1]);
+        assert!(AhoCorasick::new::<&[u8]>(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..1000 {
+            let alphabet = rng.gen_range(1..=3);
+            let text = random_string(&mut rng, alphabet, 1..=50);
+            let patterns: Vec<Vec<u8>> = (0..6)
+                .map(|_| random_string(&mut rng, alphabet, 1..=5))
+                .collect();
+            let automaton = AhoCorasick::new(&patterns);
+
+            let mut naive: Vec<(usize, usize)> = (0..text.len())
+                .flat_map(|start| (0..patterns.len()).map(move |p| (p, start)))
+                .filter(|&(p, start)| text[start..].starts_with(&patterns[p]))
+                .collect();
+            let counts: Vec<u64> = (0..patterns.len())
+                .map(|p| naive.iter().filter(|&&(q, _)| q == p).count() as u64)
+                .collect();
+            let first: Vec<Option<usize>> = (0..patterns.len())
+                .map(|p| {
+                    naive
+                        .iter()
+                        .find(|&&(q, _)| q == p)
+                        .map(|&(_, start)| start)
+                })
+                .collect();
+            assert_eq!(automaton.counts(&text), counts);
+            assert_eq!(automaton.first_positions(&text), first);
+            assert_eq!(
+                automaton.occurs(&text),
+                counts.iter().map(|&c| c > 0).collect::<Vec<_>>()
+            );
+
+            let mut found: Vec<(usize, usize)> = automaton.matches(&text).collect();
+            let ends: Vec<usize> = found
+                .iter()
+                .map(|&(p, start)| start + patterns[p].len())
+                .collect();
+            assert!(ends.windows(2).all(|pair| pair[0] <= pair[1]));
+            found.sort_unstable();
+            naive.sort_unstable();
+            assert_eq!(found, naive);
+        }
+    }
+
+    #[test]
+    fn test_many_matches() {
+        // 5 * 10^5 pattern bytes and 10^8 matches: the answers stay linear in the input
+        let text = vec![b'a'; 100_000];
+        let patterns: Vec<Vec<u8>> = (1..=1000).map(|len| vec![b'a'; len]).collect();
+        let automaton = AhoCorasick::new(&patterns);
+        let counts = automaton.counts(&text);
+        assert!((1..=1000).all(|len| counts[len - 1] == (text.len() - len + 1) as u64));
+        assert!(automaton
+            .first_positions(&text)
+            .iter()
+            .all(|&first| first == Some(0)));
+    }
+}
@@ -0,0 +1,238 @@
+//! Polynomial rolling hashes modulo the Mersenne prime 2<sup>61</sup> - 1, for Repeating Substring, Palindrome Queries
+//! and String Matching.
+//!
+//! With a base chosen at random, two different strings of length n collide with probability at most n / 2<sup>61</sup>,
+//! so a fixed anti-hash test can't target it. The base comes from the standard library's `RandomState`, which keeps
+//! the module free of `rand` and pasteable into a bin.
+
+use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::ops::Range;
+
+pub const MODULUS: u64 = (1 << 61) - 1;
+
+/// `a * b mod MODULUS`, for `a, b < MODULUS`
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn mul_mod(a: u64, b: u64) -> u64 {
+    let product = u128::from(a) * u128::from(b);
+    // 2^61 = 1, so the high bits fold back onto the low ones
+    let folded = (product as u64 & MODULUS) + (product >> 61) as u64;
+    if folded >= MODULUS {
+        folded - MODULUS
+    } else {
+        folded
+    }
+}
+
+fn add_mod(a: u64, b: u64) -> u64 {
+    let sum = a + b;
+    if sum >= MODULUS {
+        sum - MODULUS
+    } else {
+        sum
+    }
+}
+
+/// a base drawn uniformly from `256..MODULUS - 1`, above the byte values
+#[must_use]
+pub fn random_base() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    256 + hasher.finish() % (MODULUS - 257)
+}
+
+/// Hashes of every substring of a string, in O(1) each after O(n) preprocessing.
+#[derive(Clone, Debug)]
+pub struct StringHasher {
+    text: Vec<u8>,
+    base: u64,
+    /// `powers[i]` is base<sup>i</sup>
+    powers: Vec<u64>,
+    /// `prefix[i]` is the hash of `text[..i]`
+    prefix: Vec<u64>,
+    /// the same for the reversed text
+    reverse: Vec<u64>,
+}
+
+impl StringHasher {
+    /// Hashes of `text` with a random base.
+    #[must_use]
+    pub fn new(text: &[u8]) -> Self {
+        Self::with_base(text, random_base())
+    }
+
+    /// Hashes of `text` with a given base, for reproducible runs or to compare hashes of two strings.
+    ///
+    /// # Panics
+    ///   if `base` is not in `256..MODULUS`
+    #[must_use]
+    pub fn with_base(text: &[u8], base: u64) -> Self {
+        assert!((256..MODULUS).contains(&base), "base {base} out of range");
+        let mut powers = Vec::with_capacity(text.len() + 1);
+        powers.push(1);
+        for i in 0..text.len() {
+            powers.push(mul_mod(powers[i], base));
+        }
+        let prefix_hashes = |bytes: &mut dyn Iterator<Item = &u8>| {
+            let mut hashes = Vec::with_capacity(text.len() + 1);
+            hashes.push(0);
+            for (i, &byte) in bytes.enumerate() {
+                hashes.push(add_mod(mul_mod(hashes[i], base), u64::from(byte) + 1));
+            }
+            hashes
+        };
+        Self {
+            prefix: prefix_hashes(&mut text.iter()),
+            reverse: prefix_hashes(&mut text.iter().rev()),
+            text: text.to_vec(),
+            base,
+            powers,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    #[must_use]
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    /// the hash of `prefix[range]`
+    fn hash_in(&self, prefix: &[u64], range: Range<usize>) -> u64 {
+        let shifted = mul_mod(prefix[range.start], self.powers[range.len()]);
+        add_mod(prefix[range.end], MODULUS - shifted)
+    }
+
+    /// hash of `text[range]`
+    #[must_use]
+    pub fn hash(&self, range: Range<usize>) -> u64 {
+        self.hash_in(&self.prefix, range)
+    }
+
+    /// Hash of another string with the same base, equal to `hash(range)` when `text[range] == other`.
+    #[must_use]
+    pub fn hash_of(&self, other: &[u8]) -> u64 {
+        other.iter().fold(0, |hash, &byte| {
+            add_mod(mul_mod(hash, self.base), u64::from(byte) + 1)
+        })
+    }
+
+    /// Length of the longest common prefix of `text[a..]` and `text[b..]`, in O(log n).
+    #[must_use]
+    pub fn lcp(&self, a: usize, b: usize) -> usize {
+        let (mut low, mut high) = (0, self.len() - a.max(b));
+        while low < high {
+            let mid = (low + high).div_ceil(2);
+            if self.hash(a..a + mid) == self.hash(b..b + mid) {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        low
+    }
+
+    /// Lexicographic comparison of `text[a]` and `text[b]`, in O(log n).
+    #[must_use]
+    pub fn compare(&self, a: Range<usize>, b: Range<usize>) -> Ordering {
+        let common = self.lcp(a.start, b.start).min(a.len()).min(b.len());
+        match (a.start + common < a.end, b.start + common < b.end) {
+            (true, true) => self.text[a.start + common].cmp(&self.text[b.start + common]),
+            (in_a, in_b) => in_a.cmp(&in_b),
+        }
+    }
+
+    /// `text[range]` reads the same backwards (Palindrome Queries), in O(1).
+    #[must_use]
+    pub fn is_palindrome(&self, range: Range<usize>) -> bool {
+        let n = self.len();
+        self.hash(range.clone()) == self.hash_in(&self.reverse, n - range.end..n - range.start)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::string::test::random_string;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_mul_mod() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for (a, b) in [
+            (0, 0),
+            (MODULUS - 1, MODULUS - 1),
+            (1 << 60, 4),
+            (MODULUS - 1, 1),
+        ]
+        .into_iter()
+        .chain((0..1000).map(|_| (rng.gen_range(0..MODULUS), rng.gen_range(0..MODULUS))))
+        {
+            let expected = u128::from(a) * u128::from(b) % u128::from(MODULUS);
+            assert_eq!(u128::from(mul_mod(a, b)), expected, "{a} * {b}");
+        }
+        assert!((0..100).all(|_| (256..MODULUS).contains(&random_base())));
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..300 {
+            let alphabet = rng.gen_range(1..=3);
+            let text = random_string(&mut rng, alphabet, 0..=30);
+            let n = text.len();
+            let hasher = StringHasher::with_base(&text, rng.gen_range(256..MODULUS));
+            let ranges: Vec<Range<usize>> = (0..=n)
+                .flat_map(|start| (start..=n).map(move |end| start..end))
+                .collect();
+
+            for a in &ranges {
+                assert_eq!(hasher.hash(a.clone()), hasher.hash_of(&text[a.clone()]));
+                let palindrome = text[a.clone()].iter().eq(text[a.clone()].iter().rev());
+                assert_eq!(hasher.is_palindrome(a.clone()), palindrome, "{a:?}");
+                for b in ranges.iter().step_by(7) {
+                    let (x, y) = (&text[a.clone()], &text[b.clone()]);
+                    assert_eq!(hasher.hash(a.clone()) == hasher.hash(b.clone()), x == y);
+                    assert_eq!(hasher.compare(a.clone(), b.clone()), x.cmp(y));
+                }
+            }
+            for a in 0..=n {
+                for b in 0..=n {
+                    let naive = text[a..]
+                        .iter()
+                        .zip(&text[b..])
+                        .take_while(|(x, y)| x == y)
+                        .count();
+                    assert_eq!(hasher.lcp(a, b), naive);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_string_matching_example() {
+        let text = b"saippuakauppias";
+        let hasher = StringHasher::new(text);
+        let pattern = hasher.hash_of(b"pp");
+        let found: Vec<usize> = (0..=text.len() - 2)
+            .filter(|&i| hasher.hash(i..i + 2) == pattern)
+            .collect();
+        assert_eq!(found, [3, 10]);
+        assert!(hasher.is_palindrome(0..text.len()));
+        assert_eq!(hasher.lcp(3, 10), 2);
+        assert_eq!(hasher.compare(3..5, 10..12), Ordering::Equal);
+        assert_eq!(hasher.compare(3..7, 10..14), Ordering::Greater);
+        assert_eq!(hasher.compare(0..3, 1..3), Ordering::Greater);
+    }
+}
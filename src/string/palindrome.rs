@@ -0,0 +1,257 @@
+//! Palindromes: Manacher's algorithm (Longest Palindrome) and the eertree (All Palindromes, distinct palindromes).
+//!
+//! Manacher finds the maximal palindrome around each of the 2n - 1 centers in O(n), reusing the mirror image inside
+//! the rightmost palindrome found so far. The eertree has one node per distinct palindrome, linked to its longest
+//! proper palindromic suffix, and is built online by extending the longest palindromic suffix of the prefix read.
+
+use std::ops::Range;
+
+/// Maximal palindrome around every center: center `2i` is the byte `i`, center `2i + 1` is between the bytes `i` and
+/// `i + 1` (an empty range when they differ).
+#[must_use]
+pub fn maximal_palindromes(s: &[u8]) -> Vec<Range<usize>> {
+    let centers = (2 * s.len()).saturating_sub(1);
+    // radius[c]: the palindrome around c covers the centers c - radius[c]..=c + radius[c] of the interleaved string,
+    // where the odd centers are separators between the bytes
+    let mut radius = vec![0; centers];
+    let same = |a: usize, b: usize| a % 2 == 1 || s[a / 2] == s[b / 2];
+    // the rightmost palindrome found so far spans left..=right
+    let (mut left, mut right) = (0, 0);
+    for c in 0..centers {
+        let mut r = if c < right {
+            radius[left + right - c].min(right - c)
+        } else {
+            0
+        };
+        while r < c && c + r + 1 < centers && same(c - r - 1, c + r + 1) {
+            r += 1;
+        }
+        radius[c] = r;
+        if c + r > right {
+            (left, right) = (c - r, c + r);
+        }
+    }
+    // the bytes of the span are at its even positions
+    radius
+        .iter()
+        .enumerate()
+        .map(|(c, &r)| (c - r).div_ceil(2)..(c + r) / 2 + 1)
+        .collect()
+}
+
+/// Longest Palindrome: the first of the longest palindromic substrings.
+#[must_use]
+pub fn longest_palindrome(s: &[u8]) -> Range<usize> {
+    maximal_palindromes(s)
+        .into_iter()
+        .rev()
+        .max_by_key(Range::len)
+        .unwrap_or(0..0)
+}
+
+const ODD_ROOT: usize = 0;
+const EVEN_ROOT: usize = 1;
+
+/// Palindromic tree of a string, one node per distinct palindrome plus two roots of lengths -1 and 0.
+#[derive(Clone, Debug)]
+pub struct Eertree {
+    text: Vec<u8>,
+    /// palindrome lengths, unused for the odd root
+    len: Vec<usize>,
+    /// longest proper palindromic suffix
+    link: Vec<usize>,
+    /// `byte` + palindrome + `byte`
+    edges: Vec<Vec<(u8, usize)>>,
+    /// end of the first occurrence
+    first_end: Vec<usize>,
+    /// number of occurrences
+    count: Vec<u64>,
+    /// longest palindrome ending at every position
+    longest_suffix: Vec<usize>,
+}
+
+impl Eertree {
+    #[must_use]
+    pub fn new(text: &[u8]) -> Self {
+        let mut tree = Self {
+            text: text.to_vec(),
+            len: vec![0, 0],
+            link: vec![ODD_ROOT, ODD_ROOT],
+            edges: vec![vec![], vec![]],
+            first_end: vec![0, 0],
+            count: vec![0, 0],
+            longest_suffix: Vec::with_capacity(text.len()),
+        };
+        let mut last = EVEN_ROOT;
+        for (i, &byte) in text.iter().enumerate() {
+            let mut v = last;
+            while !tree.extends(v, i) {
+                v = tree.link[v];
+            }
+            last = if let Some(&(_, child)) = tree.edges[v].iter().find(|&&(b, _)| b == byte) {
+                child
+            } else {
+                let link = if v == ODD_ROOT {
+                    EVEN_ROOT
+                } else {
+                    let mut u = tree.link[v];
+                    while !tree.extends(u, i) {
+                        u = tree.link[u];
+                    }
+                    tree.edges[u]
+                        .iter()
+                        .find(|&&(b, _)| b == byte)
+                        .map_or(EVEN_ROOT, |&(_, child)| child)
+                };
+                let node = tree.len.len();
+                tree.len
+                    .push(if v == ODD_ROOT { 1 } else { tree.len[v] + 2 });
+                tree.link.push(link);
+                tree.edges.push(vec![]);
+                tree.first_end.push(i + 1);
+                tree.count.push(0);
+                tree.edges[v].push((byte, node));
+                node
+            };
+            tree.count[last] += 1;
+            tree.longest_suffix.push(tree.len[last]);
+        }
+        // an occurrence of a palindrome is one of its palindromic suffixes too, and links point to earlier nodes
+        for v in (2..tree.len.len()).rev() {
+            tree.count[tree.link[v]] += tree.count[v];
+        }
+        tree
+    }
+
+    /// the palindrome `v`, preceded by `text[i]`, ends at `i - 1`: it extends to a palindrome ending at `i`
+    fn extends(&self, v: usize, i: usize) -> bool {
+        v == ODD_ROOT || (i > self.len[v] && self.text[i - self.len[v] - 1] == self.text[i])
+    }
+
+    /// number of distinct non-empty palindromic substrings
+    #[must_use]
+    pub fn distinct(&self) -> usize {
+        self.len.len() - 2
+    }
+
+    /// Every distinct palindrome as its first occurrence and its number of occurrences, by order of first end.
+    pub fn palindromes(&self) -> impl Iterator<Item = (Range<usize>, u64)> + '_ {
+        (2..self.len.len()).map(|v| {
+            (
+                self.first_end[v] - self.len[v]..self.first_end[v],
+                self.count[v],
+            )
+        })
+    }
+
+    /// All Palindromes: the length of the longest palindrome ending at every position.
+    #[must_use]
+    pub fn longest_suffix_palindromes(&self) -> &[usize] {
+        &self.longest_suffix
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::string::test::random_string;
+
+    use std::collections::HashMap;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn is_palindrome(s: &[u8]) -> bool {
+        s.iter().eq(s.iter().rev())
+    }
+
+    #[test]
+    fn test_examples() {
+        // Longest Palindrome
+        let s = b"aybabtu";
+        assert_eq!(&s[longest_palindrome(s)], b"bab");
+        assert_eq!(longest_palindrome(b""), 0..0);
+        assert_eq!(
+            maximal_palindromes(b"abba"),
+            [0..1, 1..1, 1..2, 0..4, 2..3, 3..3, 3..4]
+        );
+        // All Palindromes
+        let tree = Eertree::new(b"ababbababaa");
+        assert_eq!(
+            tree.longest_suffix_palindromes(),
+            [1, 1, 3, 3, 2, 4, 6, 8, 5, 5, 2]
+        );
+        let tree = Eertree::new(b"aaa");
+        assert_eq!(tree.distinct(), 3);
+        assert_eq!(
+            tree.palindromes().collect::<Vec<_>>(),
+            [(0..1, 3), (0..2, 2), (0..3, 1)]
+        );
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..1000 {
+            let alphabet = rng.gen_range(1..=3);
+            let s = random_string(&mut rng, alphabet, 0..=40);
+            let n = s.len();
+
+            let maximal = maximal_palindromes(&s);
+            assert_eq!(maximal.len(), (2 * n).saturating_sub(1));
+            for (c, range) in maximal.iter().enumerate() {
+                // the range is centered on c, palindromic, and can't grow
+                assert_eq!(range.start + range.end, c + 1, "{s:?} {c}");
+                assert!(is_palindrome(&s[range.clone()]));
+                assert!(range.start == 0 || range.end == n || s[range.start - 1] != s[range.end]);
+            }
+            let longest = longest_palindrome(&s);
+            let naive = (0..=n)
+                .flat_map(|start| (start..=n).map(move |end| start..end))
+                .filter(|range| is_palindrome(&s[range.clone()]))
+                .max_by_key(|range| (range.len(), std::cmp::Reverse(range.start)))
+                .unwrap();
+            assert_eq!(longest, naive, "{s:?}");
+
+            let tree = Eertree::new(&s);
+            let mut counts: HashMap<&[u8], (u64, usize)> = HashMap::new();
+            for end in 1..=n {
+                for start in 0..end {
+                    if is_palindrome(&s[start..end]) {
+                        counts.entry(&s[start..end]).or_insert((0, start)).0 += 1;
+                    }
+                }
+            }
+            assert_eq!(tree.distinct(), counts.len());
+            for (range, count) in tree.palindromes() {
+                assert_eq!(counts[&s[range.clone()]], (count, range.start));
+            }
+            let longest_ending: Vec<usize> = (1..=n)
+                .map(|end| {
+                    (0..end)
+                        .find(|&start| is_palindrome(&s[start..end]))
+                        .map_or(0, |start| end - start)
+                })
+                .collect();
+            assert_eq!(tree.longest_suffix_palindromes(), longest_ending);
+        }
+    }
+
+    #[test]
+    fn test_long_strings() {
+        let n = 1_000_000;
+        let same = vec![b'a'; n];
+        assert_eq!(longest_palindrome(&same), 0..n);
+        let tree = Eertree::new(&same);
+        assert_eq!(tree.distinct(), n);
+        assert_eq!(
+            tree.palindromes().map(|(_, count)| count).sum::<u64>(),
+            n as u64 * (n as u64 + 1) / 2
+        );
+
+        let mut rng = StdRng::seed_from_u64(2);
+        let half: Vec<u8> = (0..n / 2).map(|_| b'a' + rng.gen_range(0..2)).collect();
+        let s: Vec<u8> = half.iter().chain(half.iter().rev()).copied().collect();
+        assert_eq!(longest_palindrome(&s), 0..n);
+        assert_eq!(Eertree::new(&s).longest_suffix_palindromes()[n - 1], n);
+    }
+}
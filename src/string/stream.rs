@@ -0,0 +1,282 @@
+//! KMP over a stream: match positions in text read chunk by chunk, for log scanning and Pattern Positions.
+//!
+//! The matcher only keeps the length of the pattern prefix ending at the last byte, so the text never has to be in
+//! memory and chunk boundaries don't change the result.
+
+use std::io::{self, Read};
+
+use super::prefix_function;
+
+/// What happens to the text of a match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    /// every occurrence, `aa` matches `aaa` at 0 and 1
+    Overlapping,
+    /// matching restarts after each occurrence, `aa` matches `aaa` at 0 only
+    NonOverlapping,
+}
+
+/// A pattern and the matching state over the text fed so far.
+#[derive(Clone, Debug)]
+pub struct Matcher {
+    pattern: Vec<u8>,
+    pi: Vec<usize>,
+    mode: Mode,
+    /// length of the pattern prefix ending at the last byte fed
+    len: usize,
+    /// number of bytes fed
+    offset: u64,
+}
+
+impl Matcher {
+    /// # Panics
+    ///   if `pattern` is empty
+    #[must_use]
+    pub fn new(pattern: &[u8], mode: Mode) -> Self {
+        assert!(!pattern.is_empty(), "empty pattern");
+        Self {
+            pattern: pattern.to_vec(),
+            pi: prefix_function(pattern),
+            mode,
+            len: 0,
+            offset: 0,
+        }
+    }
+
+    /// forget the text fed so far, offsets start from 0 again
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.offset = 0;
+    }
+
+    /// number of bytes fed so far
+    #[must_use]
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Feed the next byte of the text, returns the start offset of the occurrence ending with it.
+    pub fn feed(&mut self, byte: u8) -> Option<u64> {
+        self.offset += 1;
+        if self.len == self.pattern.len() {
+            self.len = match self.mode {
+                Mode::Overlapping => self.pi[self.len - 1],
+                Mode::NonOverlapping => 0,
+            };
+        }
+        while self.len > 0 && self.pattern[self.len] != byte {
+            self.len = self.pi[self.len - 1];
+        }
+        if self.pattern[self.len] == byte {
+            self.len += 1;
+        }
+        (self.len == self.pattern.len()).then(|| self.offset - self.pattern.len() as u64)
+    }
+
+    /// Feed a chunk of the text, returns the start offsets of the occurrences ending in it.
+    pub fn feed_chunk<'a>(&'a mut self, chunk: &'a [u8]) -> impl Iterator<Item = u64> + 'a {
+        chunk.iter().filter_map(|&byte| self.feed(byte))
+    }
+
+    /// Occurrences in the rest of `reader`, continuing from the text fed so far.
+    #[must_use]
+    pub fn read_matches<R: Read>(self, reader: R) -> ReadMatches<R> {
+        ReadMatches {
+            matcher: self,
+            reader,
+            buffer: vec![0; 1 << 16].into_boxed_slice(),
+            filled: 0,
+            consumed: 0,
+            done: false,
+        }
+    }
+}
+
+/// Start offsets of the occurrences in a `Read` source, see [`Matcher::read_matches`].
+#[derive(Debug)]
+pub struct ReadMatches<R> {
+    matcher: Matcher,
+    reader: R,
+    buffer: Box<[u8]>,
+    /// the chunk read last is `buffer[..filled]`, bytes before `consumed` went through the matcher
+    filled: usize,
+    consumed: usize,
+    /// set at the end of the source or after an error
+    done: bool,
+}
+
+impl<R> ReadMatches<R> {
+    /// The matcher, holding the state after the bytes consumed so far, the reader, and the bytes already read from it
+    /// but not fed yet: the iteration may stop in the middle of a chunk. To continue with another source, read these
+    /// bytes first.
+    #[must_use]
+    pub fn into_parts(self) -> (Matcher, R, Vec<u8>) {
+        let unread = self.buffer[self.consumed..self.filled].to_vec();
+        (self.matcher, self.reader, unread)
+    }
+}
+
+impl<R: Read> Iterator for ReadMatches<R> {
+    type Item = io::Result<u64>;
+
+    fn next(&mut self) -> Option<io::Result<u64>> {
+        while !self.done {
+            while let Some(&byte) = self.buffer[..self.filled].get(self.consumed) {
+                self.consumed += 1;
+                if let Some(start) = self.matcher.feed(byte) {
+                    return Some(Ok(start));
+                }
+            }
+            match self.reader.read(&mut self.buffer) {
+                Ok(0) => self.done = true,
+                Ok(filled) => (self.filled, self.consumed) = (filled, 0),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => {
+                    self.done = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::string::{matches, test::random_string};
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// a source handing out the text in random chunks, with spurious interruptions
+    struct Chunked {
+        text: Vec<u8>,
+        position: usize,
+        rng: StdRng,
+    }
+
+    impl Read for Chunked {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.rng.gen_ratio(1, 10) {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            let len = self
+                .rng
+                .gen_range(1..=8)
+                .min(buf.len())
+                .min(self.text.len() - self.position);
+            buf[..len].copy_from_slice(&self.text[self.position..self.position + len]);
+            self.position += len;
+            Ok(len)
+        }
+    }
+
+    fn naive(text: &[u8], pattern: &[u8], mode: Mode) -> Vec<u64> {
+        let mut found = vec![];
+        let mut start = 0;
+        while start + pattern.len() <= text.len() {
+            if text[start..].starts_with(pattern) {
+                found.push(start as u64);
+                if mode == Mode::NonOverlapping {
+                    start += pattern.len();
+                    continue;
+                }
+            }
+            start += 1;
+        }
+        found
+    }
+
+    #[test]
+    fn test_modes() {
+        let found = |pattern: &[u8], mode| {
+            Matcher::new(pattern, mode)
+                .read_matches(&b"aaaaabaaab"[..])
+                .collect::<io::Result<Vec<u64>>>()
+                .unwrap()
+        };
+        assert_eq!(found(b"aa", Mode::Overlapping), [0, 1, 2, 3, 6, 7]);
+        assert_eq!(found(b"aa", Mode::NonOverlapping), [0, 2, 6]);
+        assert_eq!(found(b"aab", Mode::NonOverlapping), [3, 7]);
+        assert_eq!(found(b"c", Mode::Overlapping), []);
+    }
+
+    #[test]
+    fn test_chunk_boundaries() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..1000 {
+            let alphabet = rng.gen_range(1..=3);
+            let text = random_string(&mut rng, alphabet, 1..=60);
+            let pattern = random_string(&mut rng, alphabet, 1..=4);
+            let overlapping: Vec<u64> = matches(&text, &pattern).map(|i| i as u64).collect();
+            assert_eq!(overlapping, naive(&text, &pattern, Mode::Overlapping));
+
+            for mode in [Mode::Overlapping, Mode::NonOverlapping] {
+                let expected = naive(&text, &pattern, mode);
+                let source = Chunked {
+                    text: text.clone(),
+                    position: 0,
+                    rng: StdRng::seed_from_u64(rng.gen()),
+                };
+                let found: Vec<u64> = Matcher::new(&pattern, mode)
+                    .read_matches(source)
+                    .collect::<io::Result<_>>()
+                    .unwrap();
+                assert_eq!(found, expected);
+
+                // the state carries over between chunks fed by hand
+                let mut matcher = Matcher::new(&pattern, mode);
+                let split = rng.gen_range(0..=text.len());
+                let mut found: Vec<u64> = matcher.feed_chunk(&text[..split]).collect();
+                found.extend(matcher.feed_chunk(&text[split..]));
+                assert_eq!(found, expected);
+                assert_eq!(matcher.offset(), text.len() as u64);
+            }
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        struct Failing;
+
+        impl Read for Failing {
+            fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+                Err(io::ErrorKind::InvalidData.into())
+            }
+        }
+
+        let mut found = Matcher::new(b"x", Mode::Overlapping).read_matches(Failing);
+        assert_eq!(
+            found.next().unwrap().unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+        assert!(found.next().is_none());
+
+        // a source chained after another keeps the offsets and the partial match
+        let mut found = Matcher::new(b"abc", Mode::Overlapping).read_matches(&b"xxab"[..]);
+        assert!(found.next().is_none());
+        let (matcher, _, unread) = found.into_parts();
+        assert!(unread.is_empty());
+        let found: Vec<u64> = matcher
+            .read_matches(&b"cabc"[..])
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(found, [2, 5]);
+    }
+
+    #[test]
+    fn test_into_parts_mid_chunk() {
+        // the whole source is read at once, the first match leaves the rest of the chunk unfed
+        let mut found = Matcher::new(b"ab", Mode::Overlapping).read_matches(&b"abxab"[..]);
+        assert_eq!(found.next().unwrap().unwrap(), 0);
+        let (matcher, reader, unread) = found.into_parts();
+        assert_eq!(matcher.offset(), 2);
+        assert!(reader.is_empty());
+        assert_eq!(unread, b"xab");
+        let found: Vec<u64> = matcher
+            .read_matches(unread.chain(&b"ab"[..]))
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(found, [3, 5]);
+    }
+}
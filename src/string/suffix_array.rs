@@ -0,0 +1,328 @@
+//! Suffix array by prefix doubling and LCP array by Kasai's algorithm, for Distinct Substrings, Repeating Substring and
+//! Substring Order I/II.
+//!
+//! Each doubling round sorts the suffixes by their first 2k bytes with two counting sorts, so construction is
+//! O(n log n) and stops as soon as all ranks are distinct.
+
+use std::ops::Range;
+
+/// Start positions of the suffixes of `text` in lexicographic order.
+#[must_use]
+pub fn suffix_array(text: &[u8]) -> Vec<usize> {
+    let n = text.len();
+    let mut suffixes: Vec<usize> = (0..n).collect();
+    suffixes.sort_by_key(|&i| text[i]);
+    let mut rank: Vec<usize> = text.iter().map(|&byte| usize::from(byte)).collect();
+    let mut next_rank = vec![0; n];
+    let mut by_second = Vec::with_capacity(n);
+    let mut count = vec![0; n.max(256) + 1];
+
+    let mut k = 1;
+    while k < n {
+        // order by the rank of the second half, the suffixes too short to have one come first
+        by_second.clear();
+        by_second.extend(n - k..n);
+        by_second.extend(suffixes.iter().filter(|&&i| i >= k).map(|&i| i - k));
+        // then a stable counting sort by the rank of the first half
+        count.fill(0);
+        for &i in &by_second {
+            count[rank[i] + 1] += 1;
+        }
+        for r in 1..count.len() {
+            count[r] += count[r - 1];
+        }
+        for &i in &by_second {
+            suffixes[count[rank[i]]] = i;
+            count[rank[i]] += 1;
+        }
+
+        let key = |i: usize| (rank[i], rank.get(i + k));
+        next_rank[suffixes[0]] = 0;
+        for pair in suffixes.windows(2) {
+            next_rank[pair[1]] = next_rank[pair[0]] + usize::from(key(pair[0]) != key(pair[1]));
+        }
+        std::mem::swap(&mut rank, &mut next_rank);
+        if rank[suffixes[n - 1]] == n - 1 {
+            break;
+        }
+        k *= 2;
+    }
+    suffixes
+}
+
+/// Kasai's algorithm: `lcp[i]` is the length of the longest common prefix of the suffixes `suffixes[i]` and
+/// `suffixes[i + 1]`, in O(n).
+#[must_use]
+pub fn lcp_array(text: &[u8], suffixes: &[usize]) -> Vec<usize> {
+    let n = text.len();
+    let mut rank = vec![0; n];
+    for (idx, &i) in suffixes.iter().enumerate() {
+        rank[i] = idx;
+    }
+    let mut lcp = vec![0; n.saturating_sub(1)];
+    // the LCP drops by at most one from the suffix at i to the one at i + 1
+    let mut len: usize = 0;
+    for i in 0..n {
+        if rank[i] + 1 == n {
+            len = 0;
+            continue;
+        }
+        let j = suffixes[rank[i] + 1];
+        while i + len < n && j + len < n && text[i + len] == text[j + len] {
+            len += 1;
+        }
+        lcp[rank[i]] = len;
+        len = len.saturating_sub(1);
+    }
+    lcp
+}
+
+/// A text with its suffix and LCP arrays.
+#[derive(Clone, Debug)]
+pub struct SuffixArray {
+    text: Vec<u8>,
+    suffixes: Vec<usize>,
+    lcp: Vec<usize>,
+    /// `length_sums[i]`: total length of the first `i` suffixes in the suffix array
+    length_sums: Vec<u64>,
+}
+
+impl SuffixArray {
+    #[must_use]
+    pub fn new(text: &[u8]) -> Self {
+        let suffixes = suffix_array(text);
+        let mut length_sums = Vec::with_capacity(text.len() + 1);
+        length_sums.push(0);
+        for (idx, &start) in suffixes.iter().enumerate() {
+            length_sums.push(length_sums[idx] + (text.len() - start) as u64);
+        }
+        Self {
+            lcp: lcp_array(text, &suffixes),
+            text: text.to_vec(),
+            suffixes,
+            length_sums,
+        }
+    }
+
+    /// start positions of the suffixes in lexicographic order
+    #[must_use]
+    pub fn suffixes(&self) -> &[usize] {
+        &self.suffixes
+    }
+
+    /// see [`lcp_array`]
+    #[must_use]
+    pub fn lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    /// LCP of the suffix at `idx` in the suffix array with the previous one, 0 for the first one
+    fn lcp_before(&self, idx: usize) -> usize {
+        idx.checked_sub(1).map_or(0, |previous| self.lcp[previous])
+    }
+
+    /// Distinct Substrings: the number of distinct non-empty substrings.
+    #[must_use]
+    pub fn distinct_substrings(&self) -> u64 {
+        let n = self.text.len();
+        (0..n)
+            .map(|idx| (n - self.suffixes[idx] - self.lcp_before(idx)) as u64)
+            .sum()
+    }
+
+    /// Repeating Substring: a longest substring occurring at least twice, empty if there is none.
+    #[must_use]
+    pub fn longest_repeated(&self) -> Range<usize> {
+        self.lcp
+            .iter()
+            .enumerate()
+            .max_by_key(|&(idx, &len)| (len, std::cmp::Reverse(idx)))
+            .map_or(0..0, |(idx, &len)| {
+                self.suffixes[idx]..self.suffixes[idx] + len
+            })
+    }
+
+    /// Substring Order I: the `k`-th (from 1) smallest distinct non-empty substring, `None` if there are fewer.
+    #[must_use]
+    pub fn kth_distinct(&self, mut k: u64) -> Option<Range<usize>> {
+        if k == 0 {
+            return None;
+        }
+        let n = self.text.len();
+        // the substrings new to each suffix are its prefixes longer than the LCP with the previous one
+        for (idx, &start) in self.suffixes.iter().enumerate() {
+            let shared = self.lcp_before(idx);
+            let new = (n - start - shared) as u64;
+            if k <= new {
+                return Some(start..start + shared + usize::try_from(k).ok()?);
+            }
+            k -= new;
+        }
+        None
+    }
+
+    /// Substring Order II: the `k`-th (from 1) smallest non-empty substring, counting every occurrence, `None` if
+    /// there are fewer. O(|answer| · σ · log n) for an alphabet of σ bytes.
+    #[must_use]
+    pub fn kth_with_repeats(&self, mut k: u64) -> Option<Range<usize>> {
+        let n = self.text.len();
+        let length_sums = &self.length_sums;
+        if k == 0 || k > length_sums[n] {
+            return None;
+        }
+
+        // the suffixes in lo..hi share a prefix of length depth, the answer starts with it
+        let (mut lo, mut hi, mut depth) = (0, n, 0);
+        loop {
+            if depth > 0 {
+                let occurrences = (hi - lo) as u64;
+                if k <= occurrences {
+                    return Some(self.suffixes[lo]..self.suffixes[lo] + depth);
+                }
+                k -= occurrences;
+            }
+            // the shared prefix may be a whole suffix, which sorts first and has nothing longer to offer
+            if self.suffixes[lo] + depth == n {
+                lo += 1;
+            }
+            // substrings extending the prefix, grouped by the next byte
+            let mut start = lo;
+            loop {
+                let byte = self.text[self.suffixes[start] + depth];
+                let end = start
+                    + self.suffixes[start..hi].partition_point(|&i| self.text[i + depth] <= byte);
+                let extensions =
+                    length_sums[end] - length_sums[start] - (depth * (end - start)) as u64;
+                if k <= extensions {
+                    (lo, hi, depth) = (start, end, depth + 1);
+                    break;
+                }
+                k -= extensions;
+                start = end;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::string::test::random_string;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn check_sorted(text: &[u8], array: &SuffixArray) {
+        let n = text.len();
+        let mut seen = vec![false; n];
+        for &i in array.suffixes() {
+            assert!(!std::mem::replace(&mut seen[i], true));
+        }
+        for (idx, pair) in array.suffixes().windows(2).enumerate() {
+            let (a, b) = (&text[pair[0]..], &text[pair[1]..]);
+            let lcp = array.lcp()[idx];
+            assert_eq!(a[..lcp], b[..lcp]);
+            assert!(lcp == a.len() || a[lcp] < b[lcp], "suffixes {pair:?}");
+        }
+    }
+
+    #[test]
+    fn test_examples() {
+        let text = b"banana";
+        let array = SuffixArray::new(text);
+        assert_eq!(array.suffixes(), [5, 3, 1, 0, 4, 2]);
+        assert_eq!(array.lcp(), [1, 3, 0, 0, 2]);
+        assert_eq!(&text[array.longest_repeated()], b"ana");
+
+        // Distinct Substrings
+        assert_eq!(SuffixArray::new(b"abaa").distinct_substrings(), 8);
+        // Repeating Substring
+        let text = b"cabababc";
+        let array = SuffixArray::new(text);
+        assert_eq!(&text[array.longest_repeated()], b"abab");
+        assert_eq!(SuffixArray::new(b"abc").longest_repeated(), 0..0);
+        // Substring Order I and II
+        let text = b"babaacbaab";
+        let array = SuffixArray::new(text);
+        assert_eq!(&text[array.kth_distinct(10).unwrap()], b"aba");
+        let text = b"baabaa";
+        assert_eq!(
+            &text[SuffixArray::new(text).kth_with_repeats(10).unwrap()],
+            b"ab"
+        );
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..500 {
+            let alphabet = rng.gen_range(1..=3);
+            let text = random_string(&mut rng, alphabet, 0..=40);
+            let n = text.len();
+            let array = SuffixArray::new(&text);
+
+            let mut naive: Vec<usize> = (0..n).collect();
+            naive.sort_by_key(|&i| &text[i..]);
+            assert_eq!(array.suffixes(), naive, "{text:?}");
+            check_sorted(&text, &array);
+
+            let mut substrings: Vec<&[u8]> = (0..n)
+                .flat_map(|i| (i + 1..=n).map(move |j| (i, j)))
+                .map(|(i, j)| &text[i..j])
+                .collect();
+            substrings.sort_unstable();
+            for (idx, substring) in substrings.iter().enumerate() {
+                let found = array.kth_with_repeats(idx as u64 + 1).unwrap();
+                assert_eq!(&text[found], *substring);
+            }
+            assert_eq!(array.kth_with_repeats(substrings.len() as u64 + 1), None);
+            assert_eq!(array.kth_with_repeats(0), None);
+
+            substrings.dedup();
+            assert_eq!(array.distinct_substrings(), substrings.len() as u64);
+            for (idx, substring) in substrings.iter().enumerate() {
+                let found = array.kth_distinct(idx as u64 + 1).unwrap();
+                assert_eq!(&text[found], *substring);
+            }
+            assert_eq!(array.kth_distinct(substrings.len() as u64 + 1), None);
+
+            let repeated = array.longest_repeated();
+            let longest = (0..n)
+                .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+                .map(|(i, j)| {
+                    text[i..]
+                        .iter()
+                        .zip(&text[j..])
+                        .take_while(|(a, b)| a == b)
+                        .count()
+                })
+                .max()
+                .unwrap_or_default();
+            assert_eq!(repeated.len(), longest);
+            let occurrences = (0..n)
+                .filter(|&i| text[i..].starts_with(&text[repeated.clone()]))
+                .count();
+            assert!(longest == 0 || occurrences >= 2);
+        }
+    }
+
+    #[test]
+    fn test_long_texts() {
+        let n = 1_000_000;
+        let mut rng = StdRng::seed_from_u64(2);
+        let random: Vec<u8> = (0..n).map(|_| b'a' + rng.gen_range(0..2)).collect();
+        check_sorted(&random, &SuffixArray::new(&random));
+
+        // the most doubling rounds, and the most substrings
+        let same = vec![b'a'; 100_000];
+        let array = SuffixArray::new(&same);
+        assert!(array.suffixes().iter().rev().copied().eq(0..same.len()));
+        assert_eq!(array.distinct_substrings(), same.len() as u64);
+        assert_eq!(array.longest_repeated().len(), same.len() - 1);
+        let total = (same.len() as u64) * (same.len() as u64 + 1) / 2;
+        assert_eq!(
+            array.kth_with_repeats(total).map(|r| r.len()),
+            Some(same.len())
+        );
+        assert_eq!(array.kth_distinct(77).map(|r| r.len()), Some(77));
+    }
+}
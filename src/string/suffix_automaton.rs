@@ -0,0 +1,344 @@
+//! Suffix automaton over bytes: the smallest automaton accepting the suffixes of a text, whose paths from the start
+//! spell every substring exactly once. For occurrence counts, Distinct Substrings, Missing String and Substring Order I.
+//!
+//! Every state is a class of substrings with the same end positions, so its occurrence count is the number of
+//! prefixes of the text in its suffix-link subtree. At most 2n - 1 states and 3n - 4 transitions, kept as sorted
+//! lists so that walks visit bytes in lexicographic order.
+
+/// the state of the empty string
+const START: usize = 0;
+
+#[derive(Clone, Debug)]
+pub struct SuffixAutomaton {
+    /// length of the longest substring of every state
+    len: Vec<usize>,
+    /// suffix link: the state of the longest suffix in another class, `START` for the start itself
+    link: Vec<usize>,
+    /// transitions sorted by byte
+    next: Vec<Vec<(u8, usize)>>,
+    /// number of end positions, the occurrence count of every substring of the state
+    occurrences: Vec<u64>,
+    /// number of strings readable from every state, the empty one included
+    paths: Vec<u64>,
+    /// states by decreasing `len`: transitions point to earlier states, suffix links to later ones
+    order: Vec<usize>,
+}
+
+impl SuffixAutomaton {
+    #[must_use]
+    pub fn new(text: &[u8]) -> Self {
+        let mut automaton = Self {
+            len: vec![0],
+            link: vec![START],
+            next: vec![vec![]],
+            occurrences: vec![0],
+            paths: vec![],
+            order: vec![],
+        };
+        let mut last = START;
+        for &byte in text {
+            last = automaton.extend(last, byte);
+        }
+
+        // counting sort by length
+        let n = text.len();
+        let mut count = vec![0; n + 2];
+        for &len in &automaton.len {
+            count[n - len + 1] += 1;
+        }
+        for i in 1..count.len() {
+            count[i] += count[i - 1];
+        }
+        let mut order = vec![0; automaton.len.len()];
+        for (v, &len) in automaton.len.iter().enumerate() {
+            order[count[n - len]] = v;
+            count[n - len] += 1;
+        }
+        for &v in &order[..order.len() - 1] {
+            let link = automaton.link[v];
+            automaton.occurrences[link] += automaton.occurrences[v];
+        }
+        let mut paths = vec![1_u64; automaton.len()];
+        for &v in &order {
+            paths[v] += automaton.next[v]
+                .iter()
+                .map(|&(_, next)| paths[next])
+                .sum::<u64>();
+        }
+        automaton.paths = paths;
+        automaton.order = order;
+        automaton
+    }
+
+    fn transition(&self, v: usize, byte: u8) -> Option<usize> {
+        let edges = &self.next[v];
+        edges
+            .binary_search_by_key(&byte, |&(b, _)| b)
+            .ok()
+            .map(|idx| edges[idx].1)
+    }
+
+    fn set_transition(&mut self, v: usize, byte: u8, target: usize) {
+        let edges = &mut self.next[v];
+        match edges.binary_search_by_key(&byte, |&(b, _)| b) {
+            Ok(idx) => edges[idx].1 = target,
+            Err(idx) => edges.insert(idx, (byte, target)),
+        }
+    }
+
+    fn add_state(
+        &mut self,
+        len: usize,
+        link: usize,
+        next: Vec<(u8, usize)>,
+        occurrences: u64,
+    ) -> usize {
+        self.len.push(len);
+        self.link.push(link);
+        self.next.push(next);
+        self.occurrences.push(occurrences);
+        self.len.len() - 1
+    }
+
+    /// append `byte` to the text whose whole state is `last`, returns the state of the new text
+    fn extend(&mut self, last: usize, byte: u8) -> usize {
+        let current = self.add_state(self.len[last] + 1, START, vec![], 1);
+        let mut v = Some(last);
+        while let Some(p) = v.filter(|&p| self.transition(p, byte).is_none()) {
+            self.set_transition(p, byte, current);
+            v = (p != START).then(|| self.link[p]);
+        }
+        let Some(p) = v else {
+            return current;
+        };
+        let q = self.transition(p, byte).unwrap_or(START);
+        if self.len[p] + 1 == self.len[q] {
+            self.link[current] = q;
+        } else {
+            // q also holds longer strings which don't end at the new position: split off the short ones
+            let clone = self.add_state(self.len[p] + 1, self.link[q], self.next[q].clone(), 0);
+            let mut v = Some(p);
+            while let Some(p) = v.filter(|&p| self.transition(p, byte) == Some(q)) {
+                self.set_transition(p, byte, clone);
+                v = (p != START).then(|| self.link[p]);
+            }
+            self.link[q] = clone;
+            self.link[current] = clone;
+        }
+        current
+    }
+
+    /// number of states, the start included
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len.len()
+    }
+
+    /// the text is empty, only the start state is left
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 1
+    }
+
+    /// state reached by reading `pattern` from the start, `None` if it is not a substring
+    #[must_use]
+    pub fn find(&self, pattern: &[u8]) -> Option<usize> {
+        pattern
+            .iter()
+            .try_fold(START, |v, &byte| self.transition(v, byte))
+    }
+
+    /// occurrence count shared by the substrings of state `v`
+    #[must_use]
+    pub fn state_occurrences(&self, v: usize) -> u64 {
+        self.occurrences[v]
+    }
+
+    /// number of (possibly overlapping) occurrences of `pattern`, in O(|pattern| log σ)
+    #[must_use]
+    pub fn occurrences(&self, pattern: &[u8]) -> u64 {
+        match self.find(pattern) {
+            // the empty string isn't counted in the start state
+            Some(START) => self.len[self.order[0]] as u64 + 1,
+            Some(v) => self.occurrences[v],
+            None => 0,
+        }
+    }
+
+    /// Distinct Substrings: the number of distinct non-empty substrings.
+    #[must_use]
+    pub fn distinct_substrings(&self) -> u64 {
+        (1..self.len())
+            .map(|v| (self.len[v] - self.len[self.link[v]]) as u64)
+            .sum()
+    }
+
+    /// Missing String: the shortest string over `alphabet` that is not a substring, the smallest one among them.
+    #[must_use]
+    pub fn shortest_missing(&self, alphabet: &[u8]) -> Vec<u8> {
+        let mut alphabet = alphabet.to_vec();
+        alphabet.sort_unstable();
+        alphabet.dedup();
+        // missing[v]: length of the shortest string not readable from v, the states it reaches come first in `order`
+        let mut missing = vec![0; self.len()];
+        for &v in &self.order {
+            missing[v] = 1 + alphabet
+                .iter()
+                .map(|&byte| self.transition(v, byte).map_or(0, |next| missing[next]))
+                .min()
+                .unwrap_or_default();
+        }
+        let mut found = vec![];
+        let mut v = Some(START);
+        while let Some(state) = v {
+            let Some(&byte) = alphabet.iter().find(|&&byte| {
+                self.transition(state, byte).map_or(0, |next| missing[next]) + 1 == missing[state]
+            }) else {
+                break;
+            };
+            found.push(byte);
+            v = self.transition(state, byte);
+        }
+        found
+    }
+
+    /// Substring Order I: the `k`-th (from 1) smallest distinct non-empty substring, `None` if there are fewer.
+    /// O(|answer| · σ).
+    #[must_use]
+    pub fn kth_distinct(&self, mut k: u64) -> Option<Vec<u8>> {
+        let paths = &self.paths;
+        if k == 0 || k >= paths[START] {
+            return None;
+        }
+        let mut found = vec![];
+        let mut v = START;
+        while k > 0 {
+            for &(byte, next) in &self.next[v] {
+                if k <= paths[next] {
+                    found.push(byte);
+                    v = next;
+                    k -= 1;
+                    break;
+                }
+                k -= paths[next];
+            }
+        }
+        Some(found)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::string::{suffix_array::SuffixArray, test::random_string};
+
+    use std::collections::BTreeSet;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn naive_occurrences(text: &[u8], pattern: &[u8]) -> u64 {
+        (0..=text.len())
+            .filter(|&i| text[i..].starts_with(pattern))
+            .count() as u64
+    }
+
+    /// the first string over `alphabet` in (length, lexicographic) order which isn't in `text`
+    fn naive_missing(text: &[u8], alphabet: &[u8]) -> Vec<u8> {
+        let mut candidates = vec![vec![]];
+        loop {
+            candidates = candidates
+                .iter()
+                .flat_map(|prefix| {
+                    alphabet.iter().map(|&byte| {
+                        let mut extended = prefix.clone();
+                        extended.push(byte);
+                        extended
+                    })
+                })
+                .collect();
+            if let Some(missing) = candidates
+                .iter()
+                .find(|candidate| naive_occurrences(text, candidate) == 0)
+            {
+                return missing.clone();
+            }
+        }
+    }
+
+    #[test]
+    fn test_examples() {
+        let automaton = SuffixAutomaton::new(b"abcbc");
+        assert_eq!(automaton.occurrences(b"bc"), 2);
+        assert_eq!(automaton.occurrences(b"cbc"), 1);
+        assert_eq!(automaton.occurrences(b"cc"), 0);
+        assert_eq!(automaton.occurrences(b""), 6);
+        // Distinct Substrings
+        assert_eq!(SuffixAutomaton::new(b"abaa").distinct_substrings(), 8);
+        // Missing String
+        let automaton = SuffixAutomaton::new(b"ACGTACGT");
+        assert_eq!(automaton.shortest_missing(b"ACGT"), b"AA");
+        // Substring Order I
+        let automaton = SuffixAutomaton::new(b"babaacbaab");
+        assert_eq!(automaton.kth_distinct(10).unwrap(), b"aba");
+        assert!(SuffixAutomaton::new(b"").is_empty());
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..500 {
+            let alphabet = rng.gen_range(1..=3);
+            let text = random_string(&mut rng, alphabet, 0..=40);
+            let n = text.len();
+            let automaton = SuffixAutomaton::new(&text);
+            assert!(automaton.len() < (2 * n).max(n + 2));
+
+            let substrings: BTreeSet<&[u8]> = (0..n)
+                .flat_map(|i| (i + 1..=n).map(move |j| (i, j)))
+                .map(|(i, j)| &text[i..j])
+                .collect();
+            assert_eq!(automaton.distinct_substrings(), substrings.len() as u64);
+            for (idx, substring) in substrings.iter().enumerate() {
+                assert_eq!(automaton.kth_distinct(idx as u64 + 1).unwrap(), *substring);
+                assert_eq!(
+                    automaton.occurrences(substring),
+                    naive_occurrences(&text, substring)
+                );
+            }
+            assert_eq!(automaton.kth_distinct(substrings.len() as u64 + 1), None);
+            assert_eq!(automaton.kth_distinct(0), None);
+
+            for _ in 0..10 {
+                let pattern = random_string(&mut rng, alphabet + 1, 0..=5);
+                assert_eq!(
+                    automaton.occurrences(&pattern),
+                    naive_occurrences(&text, &pattern)
+                );
+            }
+            let letters: Vec<u8> = (b'a'..b'a' + rng.gen_range(1..=3)).rev().collect();
+            let mut sorted = letters.clone();
+            sorted.sort_unstable();
+            assert_eq!(
+                automaton.shortest_missing(&letters),
+                naive_missing(&text, &sorted)
+            );
+        }
+    }
+
+    #[test]
+    fn test_long_text() {
+        let n = 200_000;
+        let mut rng = StdRng::seed_from_u64(2);
+        let text: Vec<u8> = (0..n).map(|_| b'a' + rng.gen_range(0..3)).collect();
+        let automaton = SuffixAutomaton::new(&text);
+        let array = SuffixArray::new(&text);
+        assert_eq!(automaton.distinct_substrings(), array.distinct_substrings());
+        for k in [1, 1000, 123_456_789, array.distinct_substrings()] {
+            let range = array.kth_distinct(k).unwrap();
+            assert_eq!(automaton.kth_distinct(k).unwrap(), &text[range]);
+        }
+        let missing = automaton.shortest_missing(b"abc");
+        assert_eq!(automaton.occurrences(&missing), 0);
+        assert!(automaton.occurrences(&missing[..missing.len() - 1]) > 0);
+    }
+}
@@ -0,0 +1,355 @@
+//! Tries: dictionary lookups along a text (Word Combinations) and maximum XOR over a set of integers.
+//!
+//! All nodes live in one arena, the way children are stored is a type parameter: [`Dense`] arrays for small fixed
+//! alphabets ([`Lowercase`] letters, or the two [`Binary`] digits of [`XorTrie`]), [`Sorted`] edge lists for arbitrary
+//! bytes.
+
+pub const MODULO: u64 = 1_000_000_007;
+
+const ROOT: usize = 0;
+
+/// Outgoing edges of a trie node.
+pub trait Children: Default {
+    fn get(&self, symbol: u8) -> Option<usize>;
+    fn insert(&mut self, symbol: u8, node: usize);
+}
+
+/// Children as an array over the symbols `FIRST..FIRST + N`, 0 for none (the root is nobody's child). Inserting a
+/// symbol outside of the alphabet panics.
+#[derive(Clone, Debug)]
+pub struct Dense<const N: usize, const FIRST: u8 = 0>([usize; N]);
+
+impl<const N: usize, const FIRST: u8> Default for Dense<N, FIRST> {
+    fn default() -> Self {
+        Self([ROOT; N])
+    }
+}
+
+impl<const N: usize, const FIRST: u8> Children for Dense<N, FIRST> {
+    fn get(&self, symbol: u8) -> Option<usize> {
+        self.0
+            .get(usize::from(symbol.wrapping_sub(FIRST)))
+            .copied()
+            .filter(|&node| node != ROOT)
+    }
+
+    fn insert(&mut self, symbol: u8, node: usize) {
+        self.0[usize::from(symbol.wrapping_sub(FIRST))] = node;
+    }
+}
+
+pub type Lowercase = Dense<26, b'a'>;
+pub type Binary = Dense<2>;
+
+/// Children as a list sorted by byte, for any alphabet in memory linear in the number of nodes.
+#[derive(Clone, Debug, Default)]
+pub struct Sorted(Vec<(u8, usize)>);
+
+impl Children for Sorted {
+    fn get(&self, symbol: u8) -> Option<usize> {
+        self.0
+            .binary_search_by_key(&symbol, |&(s, _)| s)
+            .ok()
+            .map(|idx| self.0[idx].1)
+    }
+
+    fn insert(&mut self, symbol: u8, node: usize) {
+        let idx = self.0.partition_point(|&(s, _)| s < symbol);
+        self.0.insert(idx, (symbol, node));
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Trie<C> {
+    children: Vec<C>,
+    /// number of words ending at every node
+    words: Vec<u32>,
+}
+
+impl<C: Children> Default for Trie<C> {
+    fn default() -> Self {
+        Self {
+            children: vec![C::default()],
+            words: vec![0],
+        }
+    }
+}
+
+impl<C: Children> Trie<C> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// number of nodes, the root included
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.children.len()
+    }
+
+    /// no word was inserted, not even the empty one
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 1 && self.words[ROOT] == 0
+    }
+
+    /// child of `node` by `symbol`, the root being node 0
+    #[must_use]
+    pub fn child(&self, node: usize, symbol: u8) -> Option<usize> {
+        self.children[node].get(symbol)
+    }
+
+    /// Add a word, returns its node.
+    pub fn insert(&mut self, word: impl IntoIterator<Item = u8>) -> usize {
+        let mut node = ROOT;
+        for symbol in word {
+            node = if let Some(next) = self.child(node, symbol) {
+                next
+            } else {
+                let next = self.children.len();
+                self.children.push(C::default());
+                self.words.push(0);
+                self.children[node].insert(symbol, next);
+                next
+            };
+        }
+        self.words[node] += 1;
+        node
+    }
+
+    #[must_use]
+    pub fn contains(&self, word: &[u8]) -> bool {
+        word.iter()
+            .try_fold(ROOT, |node, &symbol| self.child(node, symbol))
+            .is_some_and(|node| self.words[node] > 0)
+    }
+
+    /// Lengths of the words which are prefixes of `text`, in increasing order, once per distinct word.
+    pub fn prefix_lengths<'a>(&'a self, text: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        let nodes = std::iter::once(ROOT).chain(text.iter().scan(ROOT, |node, &symbol| {
+            *node = self.child(*node, symbol)?;
+            Some(*node)
+        }));
+        nodes
+            .enumerate()
+            .filter(|&(_, node)| self.words[node] > 0)
+            .map(|(len, _)| len)
+    }
+
+    /// length of the longest word which is a prefix of `text`
+    #[must_use]
+    pub fn longest_prefix(&self, text: &[u8]) -> Option<usize> {
+        self.prefix_lengths(text).last()
+    }
+
+    /// End positions of the words found at position `start` of `text`, in increasing order.
+    pub fn words_at<'a>(
+        &'a self,
+        text: &'a [u8],
+        start: usize,
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.prefix_lengths(&text[start..])
+            .map(move |len| start + len)
+    }
+}
+
+/// Word Combinations: the number of ways to split `text` into words of the dictionary, modulo [`MODULO`].
+/// Duplicate words count once. O(|text| · longest word).
+#[must_use]
+pub fn count_segmentations<C: Children>(dictionary: &Trie<C>, text: &[u8]) -> u64 {
+    // ways[i]: splits of text[i..]
+    let mut ways = vec![0; text.len() + 1];
+    ways[text.len()] = 1;
+    for start in (0..text.len()).rev() {
+        ways[start] = dictionary
+            .words_at(text, start)
+            .filter(|&end| end > start)
+            .fold(0, |sum, end| (sum + ways[end]) % MODULO);
+    }
+    ways[0]
+}
+
+/// Integers of `BITS` bits as paths from the highest bit, for maximum XOR queries (Maximum Xor Subarray). `BITS` is at
+/// most 64, which is checked at compile time.
+#[derive(Clone, Debug)]
+pub struct XorTrie<const BITS: u32> {
+    trie: Trie<Binary>,
+}
+
+impl<const BITS: u32> Default for XorTrie<BITS> {
+    fn default() -> Self {
+        let () = Self::FITS_IN_U64;
+        Self { trie: Trie::new() }
+    }
+}
+
+impl<const BITS: u32> XorTrie<BITS> {
+    /// evaluated by `default`, the shifts by `BITS` overflow past 64
+    const FITS_IN_U64: () = assert!(BITS <= 64, "XorTrie holds at most 64 bits");
+
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn bits(value: u64) -> impl Iterator<Item = u8> {
+        (0..BITS)
+            .rev()
+            .map(move |bit| u8::from(value >> bit & 1 == 1))
+    }
+
+    /// # Panics
+    ///   if `value` doesn't fit in `BITS` bits
+    pub fn insert(&mut self, value: u64) {
+        assert!(
+            BITS == 64 || value >> BITS == 0,
+            "{value} has more than {BITS} bits"
+        );
+        self.trie.insert(Self::bits(value));
+    }
+
+    /// largest `value ^ x` over the inserted `x`, `None` if there is none
+    #[must_use]
+    pub fn max_xor(&self, value: u64) -> Option<u64> {
+        if self.trie.is_empty() {
+            return None;
+        }
+        let mut node = ROOT;
+        let mut best = 0;
+        for (bit, wanted) in (0..BITS).rev().zip(Self::bits(!value)) {
+            // the opposite bit when possible, any path leads to an inserted value
+            node = if let Some(next) = self.trie.child(node, wanted) {
+                best |= 1 << bit;
+                next
+            } else {
+                self.trie.child(node, 1 - wanted)?
+            };
+        }
+        Some(best)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::string::test::random_string;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn naive_segmentations(words: &[Vec<u8>], text: &[u8]) -> u64 {
+        let mut ways = vec![0; text.len() + 1];
+        ways[0] = 1;
+        for end in 1..=text.len() {
+            let mut distinct: Vec<&Vec<u8>> = words.iter().filter(|w| !w.is_empty()).collect();
+            distinct.sort_unstable();
+            distinct.dedup();
+            ways[end] = distinct
+                .iter()
+                .filter(|word| text[..end].ends_with(word))
+                .map(|word| ways[end - word.len()])
+                .sum::<u64>()
+                % MODULO;
+        }
+        ways[text.len()]
+    }
+
+    fn check<C: Children>(words: &[Vec<u8>], text: &[u8]) {
+        let mut trie = Trie::<C>::new();
+        for word in words {
+            trie.insert(word.iter().copied());
+        }
+        assert!(words.iter().all(|word| trie.contains(word)));
+        for start in 0..=text.len() {
+            let mut naive: Vec<usize> = words
+                .iter()
+                .filter(|word| text[start..].starts_with(word))
+                .map(|word| start + word.len())
+                .collect();
+            naive.sort_unstable();
+            naive.dedup();
+            assert_eq!(trie.words_at(text, start).collect::<Vec<_>>(), naive);
+            assert_eq!(
+                trie.longest_prefix(&text[start..]),
+                naive.last().map(|end| end - start)
+            );
+            assert_eq!(
+                trie.contains(&text[start..]),
+                words.iter().any(|w| *w == text[start..])
+            );
+        }
+        assert_eq!(
+            count_segmentations(&trie, text),
+            naive_segmentations(words, text)
+        );
+    }
+
+    #[test]
+    fn test_word_combinations_example() {
+        let mut trie = Trie::<Lowercase>::new();
+        for word in ["ab", "abab", "c", "cb"] {
+            trie.insert(word.bytes());
+        }
+        assert_eq!(count_segmentations(&trie, b"ababc"), 2);
+        assert_eq!(trie.longest_prefix(b"ababc"), Some(4));
+        assert_eq!(trie.words_at(b"ababc", 2).collect::<Vec<_>>(), [4]);
+        assert!(!trie.contains(b"aba"));
+        assert_eq!(trie.len(), 7);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..500 {
+            let alphabet = rng.gen_range(1..=3);
+            let text = random_string(&mut rng, alphabet, 0..=30);
+            let words: Vec<Vec<u8>> = (0..8)
+                .map(|_| random_string(&mut rng, alphabet, 0..=4))
+                .collect();
+            check::<Lowercase>(&words, &text);
+            check::<Sorted>(&words, &text);
+        }
+
+        // a long text of a single letter splits like Fibonacci numbers
+        let mut trie = Trie::<Sorted>::new();
+        trie.insert(*b"a");
+        trie.insert(*b"aa");
+        let text = vec![b'a'; 5000];
+        let words = [b"a".to_vec(), b"aa".to_vec()];
+        assert_eq!(
+            count_segmentations(&trie, &text),
+            naive_segmentations(&words, &text)
+        );
+    }
+
+    #[test]
+    fn test_max_xor() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let mut trie = XorTrie::<30>::new();
+        assert_eq!(trie.max_xor(5), None);
+        let mut values = vec![];
+        for _ in 0..2000 {
+            let value = rng.gen_range(0..1 << 30);
+            trie.insert(value);
+            values.push(value);
+            let query = rng.gen_range(0..1 << 30);
+            let naive = values.iter().map(|&x| x ^ query).max();
+            assert_eq!(trie.max_xor(query), naive);
+        }
+
+        // Maximum Xor Subarray: prefix xors of 5 1 5 9, the answer is 1 ^ 5 ^ 9
+        let mut trie = XorTrie::<30>::new();
+        let mut prefix = 0;
+        trie.insert(prefix);
+        let mut best = 0;
+        for x in [5, 1, 5, 9] {
+            prefix ^= x;
+            trie.insert(prefix);
+            best = trie.max_xor(prefix).unwrap().max(best);
+        }
+        assert_eq!(best, 13);
+
+        let mut full = XorTrie::<64>::new();
+        full.insert(u64::MAX);
+        assert_eq!(full.max_xor(0), Some(u64::MAX));
+    }
+}
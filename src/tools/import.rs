@@ -0,0 +1,53 @@
+//! Unpack a CSES test case archive into `tests/data/<bin-name>/`.
+//!
+//! Usage: `cargo run --bin import -- <zip> <bin-name> [--tests]`
+//!
+//! With `--tests`, a `#[test]` per case is also generated in the bin's test module (replacing a previous import).
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use rust_cses::import::{generate_tests, unpack};
+use rust_cses::zip::Archive;
+
+const USAGE: &str = "usage: import <zip> <bin-name> [--tests]";
+
+fn main() -> std::io::Result<ExitCode> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let with_tests = args.iter().any(|arg| arg == "--tests");
+    args.retain(|arg| arg != "--tests");
+    let Ok([zip, bin]) = <[String; 2]>::try_from(args) else {
+        eprintln!("{USAGE}");
+        return Ok(ExitCode::FAILURE);
+    };
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let source_path = root.join("src/bin").join(format!("{bin}.rs"));
+    if !source_path.is_file() {
+        eprintln!("no bin named '{bin}' in src/bin");
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let data = std::fs::read(&zip)?;
+    let archive = Archive::new(&data)?;
+    let dir: PathBuf = root.join("tests/data").join(&bin);
+    let cases = unpack(&archive, &dir)?;
+    println!("unpacked {} cases into {}", cases.len(), dir.display());
+
+    if with_tests {
+        let source = std::fs::read_to_string(&source_path)?;
+        match generate_tests(&source, &bin, &cases) {
+            Ok(source) => std::fs::write(&source_path, source)?,
+            Err(message) => {
+                eprintln!("{message}");
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+        println!(
+            "generated {} tests in {}",
+            cases.len(),
+            source_path.display()
+        );
+    }
+    Ok(ExitCode::SUCCESS)
+}
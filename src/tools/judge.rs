@@ -0,0 +1,105 @@
+//! Run a solution against a directory of `N.in`/`N.out` test cases.
+//!
+//! Usage: `cargo run --bin judge -- <bin-name> <test-dir> [--time <seconds>] [--memory <megabytes>]`
+//!
+//! The limits rely on `setrlimit` and `wait4`, so the tool only runs on Linux.
+
+#[cfg(target_os = "linux")]
+use std::path::PathBuf;
+use std::process::ExitCode;
+#[cfg(target_os = "linux")]
+use std::time::Duration;
+
+#[cfg(target_os = "linux")]
+use rust_cses::judge::{build_release, find_cases, render_diff, run_case, Limits, Verdict};
+
+#[cfg(target_os = "linux")]
+const USAGE: &str = "usage: judge <bin-name> <test-dir> [--time <seconds>] [--memory <megabytes>]";
+
+#[cfg(target_os = "linux")]
+fn parse_args() -> Result<(String, PathBuf, Limits), String> {
+    let mut args = std::env::args().skip(1);
+    let mut positional = vec![];
+    let mut limits = Limits::default();
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| {
+            args.next()
+                .and_then(|value| value.parse::<f64>().ok())
+                .filter(|value| *value > 0.0)
+                .ok_or_else(|| format!("{flag} expects a positive number"))
+        };
+        match arg.as_str() {
+            "--time" => limits.time = Duration::from_secs_f64(value("--time")?),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            "--memory" => limits.memory_bytes = (value("--memory")? * f64::from(1 << 20)) as u64,
+            _ => positional.push(arg),
+        }
+    }
+
+    match <[String; 2]>::try_from(positional) {
+        Ok([bin, dir]) => Ok((bin, PathBuf::from(dir), limits)),
+        Err(_) => Err(USAGE.to_string()),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn main() -> ExitCode {
+    eprintln!("judge is Linux only");
+    ExitCode::FAILURE
+}
+
+#[cfg(target_os = "linux")]
+fn main() -> std::io::Result<ExitCode> {
+    let (bin, dir, limits) = match parse_args() {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{message}");
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    let cases = find_cases(&dir)?;
+    if cases.is_empty() {
+        eprintln!("no N.in/N.out pairs found in {}", dir.display());
+        return Ok(ExitCode::FAILURE);
+    }
+    let executable = build_release(&bin)?;
+
+    let mut accepted = 0;
+    let mut slowest = Duration::ZERO;
+    for case in &cases {
+        let execution = run_case(&executable, case, limits)?;
+        slowest = slowest.max(execution.cpu_time);
+        #[allow(clippy::cast_precision_loss)]
+        let megabytes = execution.memory_bytes as f64 / f64::from(1 << 20);
+        println!(
+            "{:>6}  {:<3}  {:>6.2}s  {:>7.1} MB",
+            case.name,
+            execution.verdict.code(),
+            execution.cpu_time.as_secs_f64(),
+            megabytes,
+        );
+        match &execution.verdict {
+            Verdict::Accepted => accepted += 1,
+            Verdict::WrongAnswer(mismatch) => {
+                let expected = std::fs::read(&case.output)?;
+                for line in render_diff(&expected, &execution.output, mismatch).lines() {
+                    println!("        {line}");
+                }
+            }
+            Verdict::RuntimeError(reason) => println!("        {reason}"),
+            Verdict::TimeLimitExceeded | Verdict::MemoryLimitExceeded => {}
+        }
+    }
+
+    println!(
+        "{accepted}/{} accepted, slowest case {:.2}s",
+        cases.len(),
+        slowest.as_secs_f64()
+    );
+    Ok(if accepted == cases.len() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
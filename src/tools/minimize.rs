@@ -0,0 +1,202 @@
+//! Shrink an input on which a solution fails, then print it as a test for the bin.
+//!
+//! Usage: `cargo run --bin minimize -- <bin-name> <input-file> (--reference <bin-name> | --checker <program> |
+//! --expected <output-file>)`
+//!
+//! Candidates are run by the judge's process runner, so shrinking only works on Linux.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+#[cfg(target_os = "linux")]
+use std::process::Command;
+use std::process::ExitCode;
+
+#[cfg(target_os = "linux")]
+use rust_cses::judge::{build_release, compare_tokens, run, Execution, Limits, Verdict};
+use rust_cses::minimize::format_test;
+#[cfg(target_os = "linux")]
+use rust_cses::minimize::{minimize, minimize_queries};
+
+const USAGE: &str = "usage: minimize <bin-name> <input-file> (--reference <bin-name> | --checker <program> | --expected <output-file>)";
+
+/// How a candidate is found to fail, besides crashing or exceeding the limits.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+enum Oracle {
+    /// the output differs from the one of a trusted bin
+    Reference(String),
+    /// `<program> <input-file> <output-file>` exits with a failure
+    Checker(PathBuf),
+    /// the output differs from a known answer, with one line per input line after the first
+    Expected(PathBuf),
+}
+
+fn parse_args() -> Result<(String, PathBuf, Oracle), String> {
+    let mut args = std::env::args().skip(1);
+    let mut positional = vec![];
+    let mut oracle = None;
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().ok_or_else(|| format!("{flag} expects a value"));
+        let parsed = match arg.as_str() {
+            "--reference" => Oracle::Reference(value("--reference")?),
+            "--checker" => Oracle::Checker(PathBuf::from(value("--checker")?)),
+            "--expected" => Oracle::Expected(PathBuf::from(value("--expected")?)),
+            _ => {
+                positional.push(arg);
+                continue;
+            }
+        };
+        if oracle.replace(parsed).is_some() {
+            return Err(USAGE.to_string());
+        }
+    }
+
+    match (<[String; 2]>::try_from(positional), oracle) {
+        (Ok([bin, input]), Some(oracle)) => Ok((bin, PathBuf::from(input), oracle)),
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// Runs executables on candidate inputs written to a scratch file.
+#[cfg(target_os = "linux")]
+struct Runner {
+    scratch: PathBuf,
+    limits: Limits,
+    /// first I/O error, reported once minimization is over
+    error: Option<std::io::Error>,
+}
+
+#[cfg(target_os = "linux")]
+impl Runner {
+    fn run(&mut self, executable: &Path, input: &[u8]) -> Option<Execution> {
+        let result = fs::write(&self.scratch, input)
+            .and_then(|()| run(executable, &self.scratch, self.limits));
+        match result {
+            Ok(execution) => Some(execution),
+            Err(error) => {
+                self.error.get_or_insert(error);
+                None
+            }
+        }
+    }
+}
+
+/// the minimized input and the target output of its test, or why minimization failed
+type Shrunk = Result<(Vec<u8>, Vec<u8>), String>;
+
+/// Shrink `input` while `oracle` still finds it failing, along with the target output of the test.
+#[cfg(target_os = "linux")]
+fn shrink(bin: &str, input: &[u8], oracle: &Oracle) -> std::io::Result<Shrunk> {
+    let executable = build_release(bin)?;
+    let mut runner = Runner {
+        scratch: std::env::temp_dir().join(format!("cses-minimize-{}.in", std::process::id())),
+        limits: Limits::default(),
+        error: None,
+    };
+
+    let result = match oracle {
+        Oracle::Reference(reference) => {
+            let reference = build_release(reference)?;
+            minimize(bin, input, |candidate| {
+                let Some(expected) = runner.run(&reference, candidate) else {
+                    return false;
+                };
+                // a candidate the reference can't handle tells nothing about the bin
+                if expected.verdict != Verdict::Accepted {
+                    return false;
+                }
+                runner.run(&executable, candidate).is_some_and(|actual| {
+                    actual.verdict != Verdict::Accepted
+                        || compare_tokens(&expected.output, &actual.output).is_some()
+                })
+            })
+            .map(|minimized| {
+                let target = runner
+                    .run(&reference, &minimized)
+                    .map(|execution| execution.output);
+                (minimized, target.unwrap_or_default())
+            })
+        }
+        Oracle::Checker(checker) => minimize(bin, input, |candidate| {
+            let Some(actual) = runner.run(&executable, candidate) else {
+                return false;
+            };
+            if actual.verdict != Verdict::Accepted {
+                return true;
+            }
+            let output = runner.scratch.with_extension("out");
+            let status = fs::write(&output, &actual.output).and_then(|()| {
+                Command::new(checker)
+                    .arg(&runner.scratch)
+                    .arg(&output)
+                    .status()
+            });
+            match status {
+                Ok(status) => !status.success(),
+                Err(error) => {
+                    runner.error.get_or_insert(error);
+                    false
+                }
+            }
+        })
+        .map(|minimized| (minimized, b"TODO: correct answer\n".to_vec())),
+        Oracle::Expected(expected) => {
+            let expected = fs::read(expected)?;
+            minimize_queries(bin, input, &expected, |candidate, expected| {
+                runner.run(&executable, candidate).is_some_and(|actual| {
+                    actual.verdict != Verdict::Accepted
+                        || compare_tokens(expected, &actual.output).is_some()
+                })
+            })
+        }
+    };
+    let _ = fs::remove_file(&runner.scratch);
+    let _ = fs::remove_file(runner.scratch.with_extension("out"));
+    match runner.error {
+        Some(error) => Err(error),
+        None => Ok(result),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+#[allow(clippy::unnecessary_wraps)]
+fn shrink(_: &str, _: &[u8], _: &Oracle) -> std::io::Result<Shrunk> {
+    Ok(Err("minimize is Linux only".to_string()))
+}
+
+fn main() -> std::io::Result<ExitCode> {
+    let (bin, input_path, oracle) = match parse_args() {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{message}");
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    let input = fs::read(&input_path)?;
+    let result = shrink(&bin, &input, &oracle)?;
+
+    let (minimized, target) = match result {
+        Ok(minimized) => minimized,
+        Err(message) => {
+            eprintln!("{message}");
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+    println!(
+        "shrunk {} bytes to {} bytes, add this to the test module of {bin}:\n",
+        input.len(),
+        minimized.len()
+    );
+    let source = fs::read_to_string(
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("src/bin/{bin}.rs")),
+    )?;
+    let target_is_str = source.contains("fn test(input: &[u8], target: &str)");
+    print!(
+        "{}",
+        format_test("test_minimized", &minimized, &target, target_is_str)
+    );
+    if matches!(oracle, Oracle::Checker(_)) {
+        eprintln!("\nthe checker gives no answer, fill in the target by hand");
+    }
+    Ok(ExitCode::SUCCESS)
+}
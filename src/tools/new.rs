@@ -0,0 +1,59 @@
+//! Create `src/bin/<CATEGORY>_<PROBLEM_NAME>.rs` from the template and a problem statement saved as text.
+//!
+//! Usage: `cargo run --bin new -- <statement-file> [--stdin-raw]`
+//!
+//! See `rust_cses::scaffold` for the format of the statement file. `--stdin-raw` makes `main` read from the template's
+//! unbuffered file descriptors instead of the locked standard handles.
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use rust_cses::registry::problem_set;
+use rust_cses::scaffold::{parse_spec, render, resolve, Entrypoint};
+
+const USAGE: &str = "usage: new <statement-file> [--stdin-raw]";
+
+fn main() -> std::io::Result<ExitCode> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let entrypoint = if args.iter().any(|arg| arg == "--stdin-raw") {
+        Entrypoint::StdinRaw
+    } else {
+        Entrypoint::ReadToEnd
+    };
+    args.retain(|arg| arg != "--stdin-raw");
+    let Ok([statement]) = <[String; 1]>::try_from(args) else {
+        eprintln!("{USAGE}");
+        return Ok(ExitCode::FAILURE);
+    };
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let template = std::fs::read_to_string(root.join("src/bin/0_cses_template.rs"))?;
+    let problems = problem_set();
+    let created = parse_spec(&std::fs::read_to_string(statement)?).and_then(|spec| {
+        let problem = resolve(&spec, &problems)?;
+        let path = root.join("src/bin").join(format!("{}.rs", problem.bin));
+        if path.exists() {
+            return Err(format!("{} already exists", path.display()));
+        }
+        Ok((
+            path,
+            render(&spec, &problem, &template, entrypoint)?,
+            problem,
+        ))
+    });
+    match created {
+        Ok((path, source, problem)) => {
+            std::fs::write(&path, source)?;
+            println!("created {}", path.display());
+            println!(
+                "its tests need a validator: add `{}::<PROBLEM_NAME>` to src/validate",
+                problem.key
+            );
+            Ok(ExitCode::SUCCESS)
+        }
+        Err(message) => {
+            eprintln!("{message}");
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
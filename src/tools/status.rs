@@ -0,0 +1,58 @@
+//! Print how much of the CSES problem set is solved, per category.
+//!
+//! Usage: `cargo run --bin status [-- --missing]`
+//!
+//! With `--missing`, the unsolved problems of every category are listed with the bin name they should get.
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use rust_cses::registry::{coverage, problem_set, solutions};
+
+const USAGE: &str = "usage: status [--missing]";
+
+fn main() -> std::io::Result<ExitCode> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let show_missing = match args.as_slice() {
+        [] => false,
+        [flag] if flag == "--missing" => true,
+        _ => {
+            eprintln!("{USAGE}");
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    let problems = problem_set();
+    let solutions = solutions(
+        &Path::new(env!("CARGO_MANIFEST_DIR")).join("src/bin"),
+        &problems,
+    )?;
+    let report = coverage(&problems, &solutions);
+
+    let width = report
+        .iter()
+        .map(|category| category.category.len())
+        .max()
+        .unwrap_or_default();
+    for category in &report {
+        println!(
+            "{:width$}  {:>3}/{}",
+            category.category, category.solved, category.total
+        );
+        if show_missing {
+            for problem in &category.missing {
+                println!("    {} ({})", problem.title, problem.bin);
+            }
+        }
+    }
+    let solved: usize = report.iter().map(|category| category.solved).sum();
+    println!("{:width$}  {solved:>3}/{}", "Total", problems.len());
+
+    for solution in solutions
+        .iter()
+        .filter(|solution| solution.problem.is_none())
+    {
+        println!("{} matches no problem of the list", solution.bin);
+    }
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,362 @@
+//! Rooted trees, the starting point of the tree problems.
+//!
+//! Nodes are `0..n`. Children are stored in CSR form (one flat array, sliced per node), and every traversal is
+//! iterative so that a path of 2 * 10<sup>5</sup> nodes doesn't overflow the stack.
+
+pub mod centroid;
+pub mod flatten;
+pub mod hld;
+pub mod lca;
+pub mod reroot;
+
+/// Parent of the root.
+pub const NO_PARENT: usize = usize::MAX;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RootedTree {
+    root: usize,
+    /// `NO_PARENT` for the root
+    parent: Vec<usize>,
+    /// children of `v` are `children[child_start[v]..child_start[v + 1]]`, in increasing order
+    child_start: Vec<usize>,
+    children: Vec<usize>,
+    depth: Vec<usize>,
+    /// nodes by increasing depth, parents before children
+    bfs: Vec<usize>,
+    subtree_size: Vec<usize>,
+}
+
+impl RootedTree {
+    /// Build the tree from the parent of every node, the root being the only node with `NO_PARENT`.
+    /// The Subordinates format (bosses of employees 2..=n) is `[NO_PARENT, b_2 - 1, ..., b_n - 1]`.
+    ///
+    /// # Panics
+    ///   if there isn't exactly one root, a parent is out of range, or the parents form a cycle
+    #[must_use]
+    pub fn from_parents(parent: Vec<usize>) -> Self {
+        let n = parent.len();
+        let mut roots = (0..n).filter(|&v| parent[v] == NO_PARENT);
+        let root = roots.next().expect("no node without a parent");
+        assert!(
+            roots.next().is_none(),
+            "more than one node without a parent"
+        );
+
+        let mut child_start = vec![0; n + 1];
+        for &p in parent.iter().filter(|&&p| p != NO_PARENT) {
+            assert!(p < n, "parent {p} out of range");
+            child_start[p + 1] += 1;
+        }
+        for v in 0..n {
+            child_start[v + 1] += child_start[v];
+        }
+        let mut next = child_start.clone();
+        let mut children = vec![0; n - 1];
+        for (v, &p) in parent.iter().enumerate().filter(|&(_, &p)| p != NO_PARENT) {
+            children[next[p]] = v;
+            next[p] += 1;
+        }
+
+        let mut depth = vec![0; n];
+        let mut bfs = Vec::with_capacity(n);
+        bfs.push(root);
+        let mut head = 0;
+        while let Some(&v) = bfs.get(head) {
+            head += 1;
+            for &c in &children[child_start[v]..child_start[v + 1]] {
+                depth[c] = depth[v] + 1;
+                bfs.push(c);
+            }
+        }
+        assert_eq!(bfs.len(), n, "the parents form a cycle");
+
+        let mut subtree_size = vec![1; n];
+        for &v in bfs[1..].iter().rev() {
+            subtree_size[parent[v]] += subtree_size[v];
+        }
+
+        Self {
+            root,
+            parent,
+            child_start,
+            children,
+            depth,
+            bfs,
+            subtree_size,
+        }
+    }
+
+    /// Build the tree from `n - 1` undirected edges, hanging it from `root`.
+    ///
+    /// # Panics
+    ///   if the edges don't form a tree over `0..n`
+    #[must_use]
+    pub fn from_edges(n: usize, edges: &[(usize, usize)], root: usize) -> Self {
+        assert!(root < n, "root {root} out of range");
+        assert_eq!(edges.len() + 1, n, "a tree has n - 1 edges");
+
+        let mut start = vec![0; n + 1];
+        for &(a, b) in edges {
+            assert!(a < n && b < n, "edge {a} {b} out of range");
+            start[a + 1] += 1;
+            start[b + 1] += 1;
+        }
+        for v in 0..n {
+            start[v + 1] += start[v];
+        }
+        let mut next = start.clone();
+        let mut adjacent = vec![0; 2 * edges.len()];
+        for &(a, b) in edges {
+            adjacent[next[a]] = b;
+            next[a] += 1;
+            adjacent[next[b]] = a;
+            next[b] += 1;
+        }
+
+        let mut parent = vec![NO_PARENT; n];
+        let mut visited = vec![false; n];
+        visited[root] = true;
+        let mut stack = vec![root];
+        while let Some(v) = stack.pop() {
+            for &u in &adjacent[start[v]..start[v + 1]] {
+                if !visited[u] {
+                    visited[u] = true;
+                    parent[u] = v;
+                    stack.push(u);
+                }
+            }
+        }
+        assert!(
+            visited.iter().all(|&v| v),
+            "the edges don't connect every node"
+        );
+        Self::from_parents(parent)
+    }
+
+    /// number of nodes
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    #[must_use]
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    /// `None` for the root
+    #[must_use]
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        Some(self.parent[v]).filter(|&p| p != NO_PARENT)
+    }
+
+    /// parent of every node, `NO_PARENT` for the root
+    #[must_use]
+    pub fn parents(&self) -> &[usize] {
+        &self.parent
+    }
+
+    /// children of `v`, in increasing order
+    #[must_use]
+    pub fn children(&self, v: usize) -> &[usize] {
+        &self.children[self.child_start[v]..self.child_start[v + 1]]
+    }
+
+    /// number of edges between `v` and the root
+    #[must_use]
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    #[must_use]
+    pub fn depths(&self) -> &[usize] {
+        &self.depth
+    }
+
+    /// number of nodes in the subtree of `v`, itself included
+    #[must_use]
+    pub fn subtree_size(&self, v: usize) -> usize {
+        self.subtree_size[v]
+    }
+
+    #[must_use]
+    pub fn subtree_sizes(&self) -> &[usize] {
+        &self.subtree_size
+    }
+
+    /// Nodes level by level from the root. Every parent comes before its children, so iterating in reverse
+    /// accumulates values bottom-up.
+    #[must_use]
+    pub fn bfs_order(&self) -> &[usize] {
+        &self.bfs
+    }
+
+    /// Depth-first order, children in increasing order. The subtree of `v` is the range of
+    /// `subtree_size(v)` nodes starting at `v`.
+    #[must_use]
+    pub fn preorder(&self) -> Vec<usize> {
+        let mut order = Vec::with_capacity(self.len());
+        let mut stack = vec![self.root];
+        while let Some(v) = stack.pop() {
+            order.push(v);
+            stack.extend(self.children(v).iter().rev());
+        }
+        order
+    }
+
+    /// Depth-first order where every node comes after its whole subtree, children in increasing order.
+    #[must_use]
+    pub fn postorder(&self) -> Vec<usize> {
+        // preorder with the children reversed, read backwards
+        let mut order = Vec::with_capacity(self.len());
+        let mut stack = vec![self.root];
+        while let Some(v) = stack.pop() {
+            order.push(v);
+            stack.extend_from_slice(self.children(v));
+        }
+        order.reverse();
+        order
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+    /// random tree with shuffled labels, shaped between a path and a star
+    pub(crate) fn random_edges(rng: &mut StdRng, n: usize) -> Vec<(usize, usize)> {
+        let mut labels: Vec<usize> = (0..n).collect();
+        labels.shuffle(rng);
+        let spread = rng.gen_range(1..=n.max(1));
+        let mut edges: Vec<(usize, usize)> = (1..n)
+            .map(|v| {
+                let p = rng.gen_range(v.saturating_sub(spread)..v);
+                (labels[p], labels[v])
+            })
+            .collect();
+        edges.shuffle(rng);
+        edges
+    }
+
+    fn naive_preorder(tree: &RootedTree, v: usize, order: &mut Vec<usize>) {
+        order.push(v);
+        for &c in tree.children(v) {
+            naive_preorder(tree, c, order);
+        }
+    }
+
+    fn naive_postorder(tree: &RootedTree, v: usize, order: &mut Vec<usize>) {
+        for &c in tree.children(v) {
+            naive_postorder(tree, c, order);
+        }
+        order.push(v);
+    }
+
+    #[test]
+    fn test_subordinates_example() {
+        // bosses 1 1 2 3 of employees 2..=5
+        let tree = RootedTree::from_parents(vec![NO_PARENT, 0, 0, 1, 2]);
+        assert_eq!(tree.root(), 0);
+        assert_eq!(tree.children(0), [1, 2]);
+        assert_eq!(tree.children(3), []);
+        assert_eq!(tree.parent(0), None);
+        assert_eq!(tree.parent(4), Some(2));
+        assert_eq!(tree.depths(), [0, 1, 1, 2, 2]);
+        assert_eq!(tree.subtree_sizes(), [5, 2, 2, 1, 1]);
+        assert_eq!(tree.bfs_order(), [0, 1, 2, 3, 4]);
+        assert_eq!(tree.preorder(), [0, 1, 3, 2, 4]);
+        assert_eq!(tree.postorder(), [3, 1, 4, 2, 0]);
+    }
+
+    #[test]
+    fn test_from_edges() {
+        let tree = RootedTree::from_edges(5, &[(0, 1), (1, 2), (1, 3), (3, 4)], 3);
+        assert_eq!(tree.parents(), [1, 3, 1, NO_PARENT, 3]);
+        assert_eq!(tree.children(3), [1, 4]);
+        assert_eq!(tree.depth(2), 2);
+        assert_eq!(tree.subtree_size(1), 3);
+
+        let single = RootedTree::from_edges(1, &[], 0);
+        assert_eq!(
+            (single.len(), single.preorder(), single.postorder()),
+            (1, vec![0], vec![0])
+        );
+    }
+
+    #[test]
+    fn test_against_recursion() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..300 {
+            let n = rng.gen_range(1..=60);
+            let edges = random_edges(&mut rng, n);
+            let root = rng.gen_range(0..n);
+            let tree = RootedTree::from_edges(n, &edges, root);
+            assert_eq!(RootedTree::from_parents(tree.parents().to_vec()), tree);
+
+            let mut preorder = vec![];
+            naive_preorder(&tree, root, &mut preorder);
+            assert_eq!(tree.preorder(), preorder);
+            let mut postorder = vec![];
+            naive_postorder(&tree, root, &mut postorder);
+            assert_eq!(tree.postorder(), postorder);
+
+            let mut position = vec![0; n];
+            for (idx, &v) in preorder.iter().enumerate() {
+                position[v] = idx;
+            }
+            for v in 0..n {
+                // depth and size match the preorder ranges
+                let subtree = &preorder[position[v]..position[v] + tree.subtree_size(v)];
+                assert!(subtree.iter().all(|&u| tree.depth(u) >= tree.depth(v)));
+                assert_eq!(
+                    subtree.len(),
+                    (0..n)
+                        .filter(|&u| {
+                            let mut ancestor = Some(u);
+                            while let Some(a) = ancestor.filter(|&a| a != v) {
+                                ancestor = tree.parent(a);
+                            }
+                            ancestor == Some(v)
+                        })
+                        .count()
+                );
+                if let Some(p) = tree.parent(v) {
+                    assert_eq!(tree.depth(v), tree.depth(p) + 1);
+                    assert!(tree.children(p).contains(&v));
+                }
+            }
+            let depths: Vec<usize> = tree.bfs_order().iter().map(|&v| tree.depth(v)).collect();
+            assert!(depths.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+    }
+
+    #[test]
+    fn test_deep_path() {
+        let n = 200_000;
+        let edges: Vec<(usize, usize)> = (1..n).map(|v| (v - 1, v)).collect();
+        let tree = RootedTree::from_edges(n, &edges, 0);
+        assert_eq!(tree.depth(n - 1), n - 1);
+        assert_eq!(tree.subtree_size(0), n);
+        assert_eq!(tree.preorder(), (0..n).collect::<Vec<_>>());
+        assert_eq!(tree.postorder(), (0..n).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "the parents form a cycle")]
+    fn test_cycle() {
+        let _ = RootedTree::from_parents(vec![NO_PARENT, 2, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the edges don't connect every node")]
+    fn test_disconnected() {
+        let _ = RootedTree::from_edges(4, &[(0, 1), (2, 3), (3, 2)], 0);
+    }
+}
@@ -0,0 +1,408 @@
+//! Centroid decomposition (Fixed-Length Paths I/II) and small-to-large merging (Distinct Colors).
+//!
+//! Removing a centroid leaves components of at most half the size, so every node belongs to O(log n) components and
+//! walking each component from its centroid costs O(n log n) overall. Everything here is iterative, the recursion
+//! of the textbook versions overflows the 2MB stack on a path.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash};
+use std::ops::RangeInclusive;
+
+use super::{RootedTree, NO_PARENT};
+
+#[derive(Clone, Debug)]
+pub struct CentroidDecomposition {
+    /// undirected adjacency in CSR form
+    start: Vec<usize>,
+    adjacent: Vec<usize>,
+    /// parent in the centroid tree, `NO_PARENT` for the first centroid
+    parent: Vec<usize>,
+    /// depth in the centroid tree, a node is removed once the decomposition reaches its level
+    level: Vec<usize>,
+    /// centroids in the order they were chosen, parents first
+    order: Vec<usize>,
+}
+
+impl CentroidDecomposition {
+    #[must_use]
+    pub fn new(tree: &RootedTree) -> Self {
+        let n = tree.len();
+        let mut start = vec![0; n + 1];
+        for v in 0..n {
+            start[v + 1] =
+                start[v] + tree.children(v).len() + usize::from(tree.parent(v).is_some());
+        }
+        let mut adjacent = Vec::with_capacity(start[n]);
+        for v in 0..n {
+            adjacent.extend(tree.parent(v));
+            adjacent.extend_from_slice(tree.children(v));
+        }
+
+        let mut decomposition = Self {
+            start,
+            adjacent,
+            parent: vec![NO_PARENT; n],
+            level: vec![usize::MAX; n],
+            order: Vec::with_capacity(n),
+        };
+        let mut size = vec![0; n];
+        // (any node of a component, centroid it hangs from)
+        let mut pending = vec![(tree.root(), NO_PARENT)];
+        while let Some((entry, centroid_parent)) = pending.pop() {
+            let level = if centroid_parent == NO_PARENT {
+                0
+            } else {
+                decomposition.level[centroid_parent] + 1
+            };
+            let component = decomposition.component(entry, level);
+            for &(v, from) in component.iter().rev() {
+                size[v] = 1 + decomposition
+                    .neighbors(v, level)
+                    .filter(|&u| u != from)
+                    .map(|u| size[u])
+                    .sum::<usize>();
+            }
+            let total = component.len();
+            // walk towards the heavy side until no neighbor holds more than half of the component
+            let mut centroid = entry;
+            let mut previous = NO_PARENT;
+            while let Some(heavier) = decomposition
+                .neighbors(centroid, level)
+                .find(|&u| u != previous && size[u] < size[centroid] && 2 * size[u] > total)
+            {
+                previous = centroid;
+                centroid = heavier;
+            }
+
+            decomposition.parent[centroid] = centroid_parent;
+            decomposition.level[centroid] = level;
+            decomposition.order.push(centroid);
+            for u in decomposition
+                .neighbors(centroid, level + 1)
+                .collect::<Vec<_>>()
+            {
+                pending.push((u, centroid));
+            }
+        }
+        decomposition
+    }
+
+    /// neighbors still present at `level`
+    fn neighbors(&self, v: usize, level: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adjacent[self.start[v]..self.start[v + 1]]
+            .iter()
+            .copied()
+            .filter(move |&u| self.level[u] >= level)
+    }
+
+    /// nodes reachable from `entry` at `level`, in BFS order with the node they were reached from
+    fn component(&self, entry: usize, level: usize) -> Vec<(usize, usize)> {
+        let mut component = vec![(entry, NO_PARENT)];
+        let mut head = 0;
+        while let Some(&(v, from)) = component.get(head) {
+            head += 1;
+            component.extend(
+                self.neighbors(v, level)
+                    .filter(|&u| u != from)
+                    .map(|u| (u, v)),
+            );
+        }
+        component
+    }
+
+    /// parent of `c` in the centroid tree, `None` for the first centroid
+    #[must_use]
+    pub fn parent(&self, c: usize) -> Option<usize> {
+        Some(self.parent[c]).filter(|&p| p != NO_PARENT)
+    }
+
+    /// depth of `c` in the centroid tree, at most log<sub>2</sub> n
+    #[must_use]
+    pub fn level(&self, c: usize) -> usize {
+        self.level[c]
+    }
+
+    /// centroids in the order they were chosen, every one after its parent
+    #[must_use]
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    #[must_use]
+    pub fn centroid_tree(&self) -> RootedTree {
+        RootedTree::from_parents(self.parent.clone())
+    }
+
+    /// Distances from `c` to the nodes of its component, one list of `(node, distance)` per neighbor of `c`
+    /// in the component (each in BFS order). The centroid itself, at distance 0, is in none of them.
+    #[must_use]
+    pub fn branches(&self, c: usize) -> Vec<Vec<(usize, usize)>> {
+        // nodes of the component are the ones removed after c
+        let level = self.level[c] + 1;
+        self.neighbors(c, level)
+            .map(|first| {
+                let mut branch = vec![(first, 1)];
+                let mut from = vec![c];
+                let mut head = 0;
+                while let Some(&(v, distance)) = branch.get(head) {
+                    let previous = from[head];
+                    head += 1;
+                    for u in self.neighbors(v, level).filter(|&u| u != previous) {
+                        branch.push((u, distance + 1));
+                        from.push(v);
+                    }
+                }
+                branch
+            })
+            .collect()
+    }
+}
+
+/// pairs `i < j` of a sorted list with `sorted[i] + sorted[j] <= limit`
+fn pairs_at_most(sorted: &[usize], limit: usize) -> u64 {
+    let mut pairs = 0;
+    let mut j = sorted.len();
+    for i in 0..sorted.len() {
+        while j > i + 1 && sorted[i] + sorted[j - 1] > limit {
+            j -= 1;
+        }
+        if j <= i + 1 {
+            break;
+        }
+        pairs += (j - i - 1) as u64;
+    }
+    pairs
+}
+
+/// pairs of a sorted list whose sum is in `lengths`
+fn pairs_in(sorted: &[usize], lengths: &RangeInclusive<usize>) -> u64 {
+    let below = match lengths.start().checked_sub(1) {
+        Some(limit) => pairs_at_most(sorted, limit),
+        None => 0,
+    };
+    pairs_at_most(sorted, *lengths.end()) - below
+}
+
+/// Fixed-Length Paths I/II: the number of paths (unordered pairs of distinct nodes) whose number of edges is in
+/// `lengths`, which should start at 1 or more.
+#[must_use]
+pub fn count_paths(tree: &RootedTree, lengths: &RangeInclusive<usize>) -> u64 {
+    let decomposition = CentroidDecomposition::new(tree);
+    let mut paths = 0;
+    for &c in decomposition.order() {
+        // pairs through c, minus the ones within a single branch which don't go through c
+        let mut all = vec![0];
+        let mut within_branches = 0;
+        for branch in decomposition.branches(c) {
+            let mut distances: Vec<usize> = branch.iter().map(|&(_, distance)| distance).collect();
+            distances.sort_unstable();
+            within_branches += pairs_in(&distances, lengths);
+            all.extend(distances);
+        }
+        all.sort_unstable();
+        paths += pairs_in(&all, lengths) - within_branches;
+    }
+    paths
+}
+
+/// A per-subtree container which can absorb another one.
+pub trait Mergeable {
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn absorb(&mut self, other: Self);
+}
+
+impl<T: Eq + Hash, S: BuildHasher> Mergeable for HashSet<T, S> {
+    fn len(&self) -> usize {
+        HashSet::len(self)
+    }
+
+    fn absorb(&mut self, other: Self) {
+        self.extend(other);
+    }
+}
+
+/// count maps, counts of equal keys add up
+impl<K: Eq + Hash, S: BuildHasher> Mergeable for HashMap<K, u64, S> {
+    fn len(&self) -> usize {
+        HashMap::len(self)
+    }
+
+    fn absorb(&mut self, other: Self) {
+        for (key, count) in other {
+            *self.entry(key).or_insert(0) += count;
+        }
+    }
+}
+
+/// Small-to-large merging ("DSU on tree"): the container of every subtree is built by absorbing the smaller
+/// containers of the children (and `single(v)`) into the largest one, which is reused, so every element moves
+/// O(log n) times. `visit` sees the container of every subtree once it is complete, children before parents.
+pub fn small_to_large<C: Mergeable>(
+    tree: &RootedTree,
+    mut single: impl FnMut(usize) -> C,
+    mut visit: impl FnMut(usize, &C),
+) {
+    let mut containers: Vec<Option<C>> = (0..tree.len()).map(|_| None).collect();
+    for &v in tree.bfs_order().iter().rev() {
+        let mut container = single(v);
+        // the children come first in reverse BFS order, so their containers are ready
+        for mut child in tree
+            .children(v)
+            .iter()
+            .filter_map(|&c| containers[c].take())
+        {
+            if child.len() > container.len() {
+                std::mem::swap(&mut child, &mut container);
+            }
+            container.absorb(child);
+        }
+        visit(v, &container);
+        containers[v] = Some(container);
+    }
+}
+
+/// Distinct Colors: the number of distinct colors in every subtree.
+#[must_use]
+pub fn distinct_counts<T: Eq + Hash + Clone>(tree: &RootedTree, colors: &[T]) -> Vec<usize> {
+    let mut counts = vec![0; tree.len()];
+    small_to_large(
+        tree,
+        |v| HashSet::from([colors[v].clone()]),
+        |v, set| counts[v] = set.len(),
+    );
+    counts
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tree::test::random_edges;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn all_distances(tree: &RootedTree) -> Vec<Vec<usize>> {
+        let lca = crate::tree::lca::BinaryLifting::new(tree);
+        (0..tree.len())
+            .map(|a| (0..tree.len()).map(|b| lca.distance(a, b)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_examples() {
+        // Fixed-Length Paths I: 1-2 2-3 3-4 3-5, k = 2
+        let tree = RootedTree::from_edges(5, &[(0, 1), (1, 2), (2, 3), (2, 4)], 0);
+        assert_eq!(count_paths(&tree, &(2..=2)), 4);
+        // Fixed-Length Paths II: same tree, 2 ≤ k ≤ 3
+        assert_eq!(count_paths(&tree, &(2..=3)), 6);
+
+        // Distinct Colors: colors 2 3 2 2 1, edges 1-2 1-3 3-4 3-5
+        let tree = RootedTree::from_edges(5, &[(0, 1), (0, 2), (2, 3), (2, 4)], 0);
+        assert_eq!(distinct_counts(&tree, &[2, 3, 2, 2, 1]), [3, 1, 2, 1, 1]);
+    }
+
+    #[test]
+    fn test_decomposition() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..=80);
+            let tree = RootedTree::from_edges(n, &random_edges(&mut rng, n), rng.gen_range(0..n));
+            let distances = all_distances(&tree);
+            let decomposition = CentroidDecomposition::new(&tree);
+            let centroids = decomposition.centroid_tree();
+            assert_eq!(decomposition.order().len(), n);
+
+            for (c, from_c) in distances.iter().enumerate() {
+                // the component of c is its subtree in the centroid tree, c splits it in halves at most
+                let component: Vec<usize> = (0..n)
+                    .filter(|&v| {
+                        let mut ancestor = Some(v);
+                        while let Some(a) = ancestor.filter(|&a| a != c) {
+                            ancestor = decomposition.parent(a);
+                        }
+                        ancestor.is_some()
+                    })
+                    .collect();
+                assert_eq!(component.len(), centroids.subtree_size(c));
+                assert!(1 << decomposition.level(c) <= n);
+
+                let branches = decomposition.branches(c);
+                let mut covered = vec![c];
+                for branch in &branches {
+                    assert!(2 * branch.len() <= component.len());
+                    for &(v, distance) in branch {
+                        assert_eq!(distance, from_c[v]);
+                        covered.push(v);
+                    }
+                }
+                covered.sort_unstable();
+                assert_eq!(covered, component);
+            }
+
+            let k = rng.gen_range(1..=n);
+            let lengths = k..=rng.gen_range(k..=n);
+            let naive = (0..n)
+                .flat_map(|a| (a + 1..n).map(move |b| (a, b)))
+                .filter(|&(a, b)| lengths.contains(&distances[a][b]))
+                .count();
+            assert_eq!(count_paths(&tree, &lengths), naive as u64);
+        }
+    }
+
+    #[test]
+    fn test_small_to_large() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..=60);
+            let tree = RootedTree::from_edges(n, &random_edges(&mut rng, n), rng.gen_range(0..n));
+            let colors: Vec<u32> = (0..n).map(|_| rng.gen_range(0..6)).collect();
+            let preorder = tree.preorder();
+            let mut position = vec![0; n];
+            for (idx, &v) in preorder.iter().enumerate() {
+                position[v] = idx;
+            }
+            let subtree = |v: usize| &preorder[position[v]..position[v] + tree.subtree_size(v)];
+
+            let distinct = distinct_counts(&tree, &colors);
+            let mut visited = 0;
+            small_to_large(
+                &tree,
+                |v| HashMap::from([(colors[v], 1)]),
+                |v, counts: &HashMap<u32, u64>| {
+                    visited += 1;
+                    for color in 0..6 {
+                        let naive = subtree(v).iter().filter(|&&u| colors[u] == color).count();
+                        assert_eq!(counts.get(&color).copied().unwrap_or(0), naive as u64);
+                    }
+                },
+            );
+            assert_eq!(visited, n);
+            for (v, &distinct) in distinct.iter().enumerate() {
+                let mut naive: Vec<u32> = subtree(v).iter().map(|&u| colors[u]).collect();
+                naive.sort_unstable();
+                naive.dedup();
+                assert_eq!(distinct, naive.len());
+            }
+        }
+    }
+
+    #[test]
+    fn test_deep_path() {
+        let n = 200_000;
+        let edges: Vec<(usize, usize)> = (1..n).map(|v| (v - 1, v)).collect();
+        let tree = RootedTree::from_edges(n, &edges, 0);
+        assert_eq!(count_paths(&tree, &(1..=1)), n as u64 - 1);
+        assert_eq!(count_paths(&tree, &(3..=4)), 2 * n as u64 - 7);
+        let decomposition = CentroidDecomposition::new(&tree);
+        assert!((0..n).all(|v| decomposition.level(v) <= 17));
+
+        let colors: Vec<usize> = (0..n).map(|v| v % 1000).collect();
+        let distinct = distinct_counts(&tree, &colors);
+        assert_eq!(
+            (distinct[0], distinct[n - 1], distinct[n - 500]),
+            (1000, 1, 500)
+        );
+    }
+}
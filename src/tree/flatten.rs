@@ -0,0 +1,255 @@
+//! Euler-tour flattening: the subtree of every node becomes a range of an array, for Subtree Queries and Path Queries.
+//!
+//! [`SubtreeSums`] keeps node values in a Fenwick tree indexed by entry time (point update, subtree sum).
+//! [`PathSums`] adds a node's value to its whole subtree range instead, so the sum of the path from the root to a node
+//! is a single point query.
+
+use super::RootedTree;
+
+/// Entry and exit times of a preorder traversal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EulerTour {
+    tin: Vec<usize>,
+    tout: Vec<usize>,
+    order: Vec<usize>,
+}
+
+impl EulerTour {
+    #[must_use]
+    pub fn new(tree: &RootedTree) -> Self {
+        let order = tree.preorder();
+        let mut tin = vec![0; tree.len()];
+        for (idx, &v) in order.iter().enumerate() {
+            tin[v] = idx;
+        }
+        let tout = (0..tree.len())
+            .map(|v| tin[v] + tree.subtree_size(v))
+            .collect();
+        Self { tin, tout, order }
+    }
+
+    /// position of `v` in the flattened array
+    #[must_use]
+    pub fn tin(&self, v: usize) -> usize {
+        self.tin[v]
+    }
+
+    /// end of the subtree of `v`: it covers `tin(v)..tout(v)`
+    #[must_use]
+    pub fn tout(&self, v: usize) -> usize {
+        self.tout[v]
+    }
+
+    /// `u` is in the subtree of `v` (itself included)
+    #[must_use]
+    pub fn is_ancestor(&self, v: usize, u: usize) -> bool {
+        self.tin[v] <= self.tin[u] && self.tin[u] < self.tout[v]
+    }
+
+    /// nodes of the flattened array, in preorder
+    #[must_use]
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+}
+
+/// Fenwick tree over `i64` sums, 0-indexed from the outside. Unlike the `FenwickTree` of `search_josephus_problem_2`
+/// (point updates and a search by prefix sum), it answers range sums.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fenwick {
+    /// 1-indexed, `tree[i]` covers the `i & i.wrapping_neg()` positions ending at `i`
+    tree: Vec<i64>,
+}
+
+impl Fenwick {
+    /// O(n) construction from initial values.
+    #[must_use]
+    pub fn new(values: &[i64]) -> Self {
+        let mut tree = vec![0; values.len() + 1];
+        tree[1..].copy_from_slice(values);
+        for i in 1..tree.len() {
+            let parent = i + (i & i.wrapping_neg());
+            if parent < tree.len() {
+                tree[parent] += tree[i];
+            }
+        }
+        Self { tree }
+    }
+
+    pub fn add(&mut self, idx: usize, delta: i64) {
+        let mut i = idx + 1;
+        while i < self.tree.len() {
+            self.tree[i] += delta;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// sum of the values in `0..end`
+    #[must_use]
+    pub fn prefix_sum(&self, end: usize) -> i64 {
+        let mut sum = 0;
+        let mut i = end;
+        while i > 0 {
+            sum += self.tree[i];
+            i &= i - 1;
+        }
+        sum
+    }
+
+    /// sum of the values in `from..to`
+    #[must_use]
+    pub fn range_sum(&self, from: usize, to: usize) -> i64 {
+        self.prefix_sum(to) - self.prefix_sum(from)
+    }
+}
+
+/// Node values with point updates and subtree sums (Subtree Queries).
+#[derive(Clone, Debug)]
+pub struct SubtreeSums {
+    tour: EulerTour,
+    values: Vec<i64>,
+    fenwick: Fenwick,
+}
+
+impl SubtreeSums {
+    #[must_use]
+    pub fn new(tree: &RootedTree, values: &[i64]) -> Self {
+        let tour = EulerTour::new(tree);
+        let flat: Vec<i64> = tour.order().iter().map(|&v| values[v]).collect();
+        Self {
+            fenwick: Fenwick::new(&flat),
+            values: values.to_vec(),
+            tour,
+        }
+    }
+
+    pub fn set(&mut self, v: usize, value: i64) {
+        self.fenwick.add(self.tour.tin(v), value - self.values[v]);
+        self.values[v] = value;
+    }
+
+    #[must_use]
+    pub fn subtree_sum(&self, v: usize) -> i64 {
+        self.fenwick.range_sum(self.tour.tin(v), self.tour.tout(v))
+    }
+}
+
+/// Node values with point updates and sums over the path from the root (Path Queries).
+#[derive(Clone, Debug)]
+pub struct PathSums {
+    tour: EulerTour,
+    values: Vec<i64>,
+    /// the difference array of "sum from the root", so a range update is two point updates
+    fenwick: Fenwick,
+}
+
+impl PathSums {
+    #[must_use]
+    pub fn new(tree: &RootedTree, values: &[i64]) -> Self {
+        let tour = EulerTour::new(tree);
+        let mut differences = vec![0; tree.len() + 1];
+        for v in 0..tree.len() {
+            differences[tour.tin(v)] += values[v];
+            differences[tour.tout(v)] -= values[v];
+        }
+        differences.pop();
+        Self {
+            fenwick: Fenwick::new(&differences),
+            values: values.to_vec(),
+            tour,
+        }
+    }
+
+    pub fn set(&mut self, v: usize, value: i64) {
+        let delta = value - self.values[v];
+        self.values[v] = value;
+        self.fenwick.add(self.tour.tin(v), delta);
+        if self.tour.tout(v) < self.values.len() {
+            self.fenwick.add(self.tour.tout(v), -delta);
+        }
+    }
+
+    /// sum of the values from the root down to `v`, both included
+    #[must_use]
+    pub fn path_sum(&self, v: usize) -> i64 {
+        self.fenwick.prefix_sum(self.tour.tin(v) + 1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tree::test::random_edges;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_fenwick() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut values: Vec<i64> = (0..100).map(|_| rng.gen_range(-50..50)).collect();
+        let mut fenwick = Fenwick::new(&values);
+        for _ in 0..1000 {
+            let idx = rng.gen_range(0..values.len());
+            let delta = rng.gen_range(-50..50);
+            values[idx] += delta;
+            fenwick.add(idx, delta);
+            let from = rng.gen_range(0..=values.len());
+            let to = rng.gen_range(from..=values.len());
+            assert_eq!(fenwick.range_sum(from, to), values[from..to].iter().sum());
+        }
+    }
+
+    #[test]
+    fn test_subtree_queries_example() {
+        // values 4 2 5 2 1, edges 1-2 1-3 3-4 3-5
+        let tree = RootedTree::from_edges(5, &[(0, 1), (0, 2), (2, 3), (2, 4)], 0);
+        let mut sums = SubtreeSums::new(&tree, &[4, 2, 5, 2, 1]);
+        assert_eq!(sums.subtree_sum(2), 8);
+        sums.set(4, 3);
+        assert_eq!(sums.subtree_sum(2), 10);
+        assert_eq!(sums.subtree_sum(0), 16);
+
+        let mut paths = PathSums::new(&tree, &[4, 2, 5, 2, 1]);
+        assert_eq!(paths.path_sum(2), 9);
+        paths.set(4, 2);
+        assert_eq!(paths.path_sum(4), 11);
+        assert_eq!(paths.path_sum(1), 6);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..=50);
+            let tree = RootedTree::from_edges(n, &random_edges(&mut rng, n), rng.gen_range(0..n));
+            let tour = EulerTour::new(&tree);
+            let mut values: Vec<i64> = (0..n).map(|_| rng.gen_range(1..=1_000_000_000)).collect();
+            let mut subtree = SubtreeSums::new(&tree, &values);
+            let mut path = PathSums::new(&tree, &values);
+
+            for _ in 0..50 {
+                let v = rng.gen_range(0..n);
+                values[v] = rng.gen_range(1..=1_000_000_000);
+                subtree.set(v, values[v]);
+                path.set(v, values[v]);
+
+                for v in 0..n {
+                    let mut ancestors = vec![v];
+                    while let Some(p) = tree.parent(ancestors[ancestors.len() - 1]) {
+                        ancestors.push(p);
+                    }
+                    let below: Vec<usize> = (0..n).filter(|&u| tour.is_ancestor(v, u)).collect();
+                    for u in 0..n {
+                        assert_eq!(tour.is_ancestor(u, v), ancestors.contains(&u));
+                    }
+                    assert_eq!(below.len(), tree.subtree_size(v));
+                    assert_eq!(
+                        subtree.subtree_sum(v),
+                        below.iter().map(|&u| values[u]).sum()
+                    );
+                    assert_eq!(path.path_sum(v), ancestors.iter().map(|&u| values[u]).sum());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,330 @@
+//! Heavy-light decomposition: any path splits into O(log n) ranges of an array, for Path Queries II style problems.
+//!
+//! Every node continues the chain of its heavy child (the one with the largest subtree), so a path from a node up to
+//! the root changes chain at most log n times. Nodes are laid out chain by chain in a preorder, which keeps subtrees
+//! contiguous too. The values live in any [`RangeStructure`], [`SegmentTree`] with [`Max`] or [`Sum`] covers the CSES
+//! problems.
+
+use std::marker::PhantomData;
+use std::ops::Range;
+
+use super::RootedTree;
+
+/// Values combined along a path. The path direction isn't kept, so `combine` should be commutative.
+pub trait Monoid {
+    type Value: Clone;
+
+    fn identity() -> Self::Value;
+    fn combine(a: &Self::Value, b: &Self::Value) -> Self::Value;
+}
+
+pub struct Max;
+
+impl Monoid for Max {
+    type Value = i64;
+
+    fn identity() -> i64 {
+        i64::MIN
+    }
+
+    fn combine(a: &i64, b: &i64) -> i64 {
+        *a.max(b)
+    }
+}
+
+pub struct Sum;
+
+impl Monoid for Sum {
+    type Value = i64;
+
+    fn identity() -> i64 {
+        0
+    }
+
+    fn combine(a: &i64, b: &i64) -> i64 {
+        a + b
+    }
+}
+
+/// An array with point assignment and range queries, where the decomposition stores the node values.
+pub trait RangeStructure {
+    type Monoid: Monoid;
+
+    fn from_values(values: Vec<<Self::Monoid as Monoid>::Value>) -> Self;
+    fn set(&mut self, idx: usize, value: <Self::Monoid as Monoid>::Value);
+    /// combined values of `range`, the identity if it's empty
+    fn query(&self, range: Range<usize>) -> <Self::Monoid as Monoid>::Value;
+}
+
+/// Bottom-up segment tree.
+#[derive(Clone, Debug)]
+pub struct SegmentTree<M: Monoid> {
+    /// leaves at `len..2 * len`, node `i` combines `2 * i` and `2 * i + 1`
+    nodes: Vec<M::Value>,
+    len: usize,
+    monoid: PhantomData<M>,
+}
+
+impl<M: Monoid> RangeStructure for SegmentTree<M> {
+    type Monoid = M;
+
+    fn from_values(values: Vec<M::Value>) -> Self {
+        let len = values.len();
+        let mut nodes = vec![M::identity(); len];
+        nodes.extend(values);
+        for i in (1..len).rev() {
+            nodes[i] = M::combine(&nodes[2 * i], &nodes[2 * i + 1]);
+        }
+        Self {
+            nodes,
+            len,
+            monoid: PhantomData,
+        }
+    }
+
+    fn set(&mut self, idx: usize, value: M::Value) {
+        let mut i = idx + self.len;
+        self.nodes[i] = value;
+        while i > 1 {
+            i /= 2;
+            self.nodes[i] = M::combine(&self.nodes[2 * i], &self.nodes[2 * i + 1]);
+        }
+    }
+
+    fn query(&self, range: Range<usize>) -> M::Value {
+        let mut result = M::identity();
+        let (mut from, mut to) = (range.start + self.len, range.end + self.len);
+        while from < to {
+            if from & 1 == 1 {
+                result = M::combine(&result, &self.nodes[from]);
+                from += 1;
+            }
+            if to & 1 == 1 {
+                to -= 1;
+                result = M::combine(&result, &self.nodes[to]);
+            }
+            from /= 2;
+            to /= 2;
+        }
+        result
+    }
+}
+
+type Value<S> = <<S as RangeStructure>::Monoid as Monoid>::Value;
+
+/// Node values on a tree, with point updates and path or subtree queries.
+#[derive(Clone, Debug)]
+pub struct HeavyLight<S> {
+    parent: Vec<usize>,
+    depth: Vec<usize>,
+    /// top node of the chain of every node
+    head: Vec<usize>,
+    /// index of every node in the range structure
+    position: Vec<usize>,
+    subtree_size: Vec<usize>,
+    values: S,
+}
+
+impl<S: RangeStructure> HeavyLight<S> {
+    #[must_use]
+    pub fn new(tree: &RootedTree, values: &[Value<S>]) -> Self {
+        let n = tree.len();
+        let heavy = |v: usize| {
+            tree.children(v)
+                .iter()
+                .copied()
+                .max_by_key(|&c| tree.subtree_size(c))
+        };
+
+        let mut head = vec![tree.root(); n];
+        let mut position = vec![0; n];
+        let mut laid_out: Vec<Value<S>> = Vec::with_capacity(n);
+        let mut stack = vec![tree.root()];
+        while let Some(v) = stack.pop() {
+            position[v] = laid_out.len();
+            laid_out.push(values[v].clone());
+            let heavy_child = heavy(v);
+            for &c in tree.children(v) {
+                if Some(c) != heavy_child {
+                    head[c] = c;
+                    stack.push(c);
+                }
+            }
+            // popped next, so the chain stays contiguous
+            if let Some(c) = heavy_child {
+                head[c] = head[v];
+                stack.push(c);
+            }
+        }
+
+        Self {
+            parent: (0..n).map(|v| tree.parent(v).unwrap_or(v)).collect(),
+            depth: tree.depths().to_vec(),
+            head,
+            position,
+            subtree_size: tree.subtree_sizes().to_vec(),
+            values: S::from_values(laid_out),
+        }
+    }
+
+    pub fn set(&mut self, v: usize, value: Value<S>) {
+        self.values.set(self.position[v], value);
+    }
+
+    /// ranges of the range structure covering the path between `a` and `b`, and their LCA
+    fn path_ranges(&self, mut a: usize, mut b: usize) -> (Vec<Range<usize>>, usize) {
+        let mut ranges = vec![];
+        while self.head[a] != self.head[b] {
+            if self.depth[self.head[a]] < self.depth[self.head[b]] {
+                std::mem::swap(&mut a, &mut b);
+            }
+            ranges.push(self.position[self.head[a]]..self.position[a] + 1);
+            a = self.parent[self.head[a]];
+        }
+        let (top, bottom) = if self.depth[a] <= self.depth[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        ranges.push(self.position[top]..self.position[bottom] + 1);
+        (ranges, top)
+    }
+
+    #[must_use]
+    pub fn lca(&self, a: usize, b: usize) -> usize {
+        self.path_ranges(a, b).1
+    }
+
+    /// combined values of the nodes on the path between `a` and `b`, both included
+    #[must_use]
+    pub fn path_query(&self, a: usize, b: usize) -> Value<S> {
+        self.path_ranges(a, b)
+            .0
+            .into_iter()
+            .fold(S::Monoid::identity(), |acc, range| {
+                S::Monoid::combine(&acc, &self.values.query(range))
+            })
+    }
+
+    /// combined values of the subtree of `v`
+    #[must_use]
+    pub fn subtree_query(&self, v: usize) -> Value<S> {
+        let from = self.position[v];
+        self.values.query(from..from + self.subtree_size[v])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tree::test::random_edges;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    /// a plain array, to show any structure plugs in (and as a reference)
+    struct Naive<M: Monoid>(Vec<M::Value>);
+
+    impl<M: Monoid> RangeStructure for Naive<M> {
+        type Monoid = M;
+
+        fn from_values(values: Vec<M::Value>) -> Self {
+            Self(values)
+        }
+
+        fn set(&mut self, idx: usize, value: M::Value) {
+            self.0[idx] = value;
+        }
+
+        fn query(&self, range: Range<usize>) -> M::Value {
+            self.0[range]
+                .iter()
+                .fold(M::identity(), |acc, value| M::combine(&acc, value))
+        }
+    }
+
+    fn naive_path(tree: &RootedTree, mut a: usize, mut b: usize) -> Vec<usize> {
+        let mut path = vec![];
+        while a != b {
+            if tree.depth(a) >= tree.depth(b) {
+                path.push(a);
+                a = tree.parent(a).unwrap();
+            } else {
+                path.push(b);
+                b = tree.parent(b).unwrap();
+            }
+        }
+        path.push(a);
+        path
+    }
+
+    #[test]
+    fn test_path_queries_2_example() {
+        // values 2 4 1 3 3, edges 1-2 1-3 3-4 3-5
+        let tree = RootedTree::from_edges(5, &[(0, 1), (0, 2), (2, 3), (2, 4)], 0);
+        let mut hld = HeavyLight::<SegmentTree<Max>>::new(&tree, &[2, 4, 1, 3, 3]);
+        assert_eq!(hld.path_query(1, 3), 4);
+        assert_eq!(hld.path_query(1, 4), 4);
+        hld.set(1, 1);
+        assert_eq!(hld.path_query(1, 3), 3);
+        assert_eq!(hld.lca(3, 4), 2);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..=60);
+            let tree = RootedTree::from_edges(n, &random_edges(&mut rng, n), rng.gen_range(0..n));
+            let mut values: Vec<i64> = (0..n).map(|_| rng.gen_range(-1000..=1000)).collect();
+            let mut max = HeavyLight::<SegmentTree<Max>>::new(&tree, &values);
+            let mut sum = HeavyLight::<SegmentTree<Sum>>::new(&tree, &values);
+            let mut naive = HeavyLight::<Naive<Sum>>::new(&tree, &values);
+
+            for _ in 0..200 {
+                let v = rng.gen_range(0..n);
+                values[v] = rng.gen_range(-1000..=1000);
+                max.set(v, values[v]);
+                sum.set(v, values[v]);
+                naive.set(v, values[v]);
+
+                let (a, b) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                let path = naive_path(&tree, a, b);
+                let lca = *path.iter().min_by_key(|&&v| tree.depth(v)).unwrap();
+                assert_eq!(max.lca(a, b), lca);
+                assert_eq!(
+                    max.path_query(a, b),
+                    path.iter().map(|&v| values[v]).max().unwrap()
+                );
+                assert_eq!(sum.path_query(a, b), path.iter().map(|&v| values[v]).sum());
+                assert_eq!(naive.path_query(a, b), sum.path_query(a, b));
+
+                let subtree: i64 = (0..n)
+                    .filter(|&u| {
+                        // v is the top of the path
+                        naive_path(&tree, u, v)
+                            .iter()
+                            .all(|&w| tree.depth(w) >= tree.depth(v))
+                    })
+                    .map(|u| values[u])
+                    .sum();
+                assert_eq!(sum.subtree_query(v), subtree);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_changes() {
+        // a complete binary tree is the worst case for the number of chains on a path
+        let n = (1 << 17) - 1;
+        let edges: Vec<(usize, usize)> = (1..n).map(|v| ((v - 1) / 2, v)).collect();
+        let tree = RootedTree::from_edges(n, &edges, 0);
+        let hld = HeavyLight::<SegmentTree<Sum>>::new(&tree, &vec![1; n]);
+        for (a, b) in [(n - 1, n / 2), (n - 1, n - 2), (n / 2 + 1, n / 2)] {
+            assert!(hld.path_ranges(a, b).0.len() <= 2 * 17);
+            assert_eq!(
+                hld.path_query(a, b),
+                i64::try_from(naive_path(&tree, a, b).len()).unwrap()
+            );
+        }
+    }
+}
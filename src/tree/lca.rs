@@ -0,0 +1,269 @@
+//! Lowest common ancestors, for Company Queries I/II, Distance Queries and Counting Paths.
+//!
+//! [`BinaryLifting`] also answers k-th ancestor queries in O(log n), [`EulerTourLca`] answers LCA queries in O(1)
+//! after an O(n log n) sparse table.
+
+use super::RootedTree;
+
+/// `distance(a, b)` from the depths and the LCA
+fn distance(depth: &[usize], a: usize, b: usize, lca: usize) -> usize {
+    depth[a] + depth[b] - 2 * depth[lca]
+}
+
+/// Ancestors at every power of two distance.
+#[derive(Clone, Debug)]
+pub struct BinaryLifting {
+    /// `up[j * n + v]` is the 2<sup>j</sup>-th ancestor of `v`, or the root when it's higher than the root
+    up: Vec<usize>,
+    depth: Vec<usize>,
+}
+
+impl BinaryLifting {
+    #[must_use]
+    pub fn new(tree: &RootedTree) -> Self {
+        let n = tree.len();
+        let max_depth = tree.depths().iter().copied().max().unwrap_or_default();
+        let levels = (usize::BITS - max_depth.leading_zeros()).max(1) as usize;
+
+        let mut up = Vec::with_capacity(levels * n);
+        up.extend((0..n).map(|v| tree.parent(v).unwrap_or(v)));
+        for j in 1..levels {
+            for v in 0..n {
+                let half = up[(j - 1) * n + v];
+                up.push(up[(j - 1) * n + half]);
+            }
+        }
+        Self {
+            up,
+            depth: tree.depths().to_vec(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.depth.len()
+    }
+
+    /// Ancestor `k` levels above `v`, `v` itself for `k = 0`, `None` if `k` is larger than the depth of `v`.
+    #[must_use]
+    pub fn kth_ancestor(&self, mut v: usize, k: usize) -> Option<usize> {
+        if k > self.depth[v] {
+            return None;
+        }
+        let n = self.len();
+        let mut remaining = k;
+        while remaining != 0 {
+            let j = remaining.trailing_zeros() as usize;
+            v = self.up[j * n + v];
+            remaining &= remaining - 1;
+        }
+        Some(v)
+    }
+
+    #[must_use]
+    pub fn lca(&self, a: usize, b: usize) -> usize {
+        let (mut a, mut b) = if self.depth[a] >= self.depth[b] {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        a = self
+            .kth_ancestor(a, self.depth[a] - self.depth[b])
+            .unwrap_or(a);
+        if a == b {
+            return a;
+        }
+        let n = self.len();
+        for j in (0..self.up.len() / n).rev() {
+            let (up_a, up_b) = (self.up[j * n + a], self.up[j * n + b]);
+            if up_a != up_b {
+                a = up_a;
+                b = up_b;
+            }
+        }
+        self.up[a]
+    }
+
+    /// number of edges on the path between `a` and `b`
+    #[must_use]
+    pub fn distance(&self, a: usize, b: usize) -> usize {
+        distance(&self.depth, a, b, self.lca(a, b))
+    }
+}
+
+/// The LCA of `a` and `b` is the shallowest node of the Euler tour between their first visits.
+#[derive(Clone, Debug)]
+pub struct EulerTourLca {
+    /// index of the first visit of every node in the tour
+    first: Vec<usize>,
+    /// `table[j][i]` is the shallowest node of the tour in `i..i + 2^j`
+    table: Vec<Vec<usize>>,
+    depth: Vec<usize>,
+}
+
+impl EulerTourLca {
+    #[must_use]
+    pub fn new(tree: &RootedTree) -> Self {
+        let n = tree.len();
+        let mut first = vec![0; n];
+        let mut tour = Vec::with_capacity(2 * n);
+        // (node, index of the next child to visit), a node goes in the tour before its first child and after each child
+        let mut stack = vec![(tree.root(), 0)];
+        while let Some((v, next)) = stack.last_mut() {
+            let v = *v;
+            if *next == 0 {
+                first[v] = tour.len();
+            }
+            tour.push(v);
+            if let Some(&child) = tree.children(v).get(*next) {
+                *next += 1;
+                stack.push((child, 0));
+            } else {
+                stack.pop();
+            }
+        }
+
+        let depth = tree.depths();
+        let shallowest = |a: usize, b: usize| if depth[a] <= depth[b] { a } else { b };
+        let mut table = vec![tour];
+        let mut width = 1;
+        while 2 * width <= table[0].len() {
+            let previous = &table[table.len() - 1];
+            let level = (0..previous.len() - width)
+                .map(|i| shallowest(previous[i], previous[i + width]))
+                .collect();
+            table.push(level);
+            width *= 2;
+        }
+        Self {
+            first,
+            table,
+            depth: depth.to_vec(),
+        }
+    }
+
+    #[must_use]
+    pub fn lca(&self, a: usize, b: usize) -> usize {
+        let (from, to) = {
+            let (x, y) = (self.first[a], self.first[b]);
+            (x.min(y), x.max(y) + 1)
+        };
+        let j = (usize::BITS - 1 - (to - from).leading_zeros()) as usize;
+        let (left, right) = (self.table[j][from], self.table[j][to - (1 << j)]);
+        if self.depth[left] <= self.depth[right] {
+            left
+        } else {
+            right
+        }
+    }
+
+    /// number of edges on the path between `a` and `b`
+    #[must_use]
+    pub fn distance(&self, a: usize, b: usize) -> usize {
+        distance(&self.depth, a, b, self.lca(a, b))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tree::{test::random_edges, NO_PARENT};
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn naive_kth_ancestor(tree: &RootedTree, mut v: usize, k: usize) -> Option<usize> {
+        for _ in 0..k {
+            v = tree.parent(v)?;
+        }
+        Some(v)
+    }
+
+    fn naive_lca(tree: &RootedTree, mut a: usize, mut b: usize) -> usize {
+        while tree.depth(a) > tree.depth(b) {
+            a = tree.parent(a).unwrap();
+        }
+        while tree.depth(b) > tree.depth(a) {
+            b = tree.parent(b).unwrap();
+        }
+        while a != b {
+            a = tree.parent(a).unwrap();
+            b = tree.parent(b).unwrap();
+        }
+        a
+    }
+
+    fn check(tree: &RootedTree, queries: impl Iterator<Item = (usize, usize, usize)>) {
+        let lifting = BinaryLifting::new(tree);
+        let euler = EulerTourLca::new(tree);
+        for (a, b, k) in queries {
+            let lca = naive_lca(tree, a, b);
+            assert_eq!(lifting.lca(a, b), lca, "lca({a}, {b})");
+            assert_eq!(euler.lca(a, b), lca, "lca({a}, {b})");
+            let distance = tree.depth(a) + tree.depth(b) - 2 * tree.depth(lca);
+            assert_eq!(lifting.distance(a, b), distance);
+            assert_eq!(euler.distance(a, b), distance);
+            assert_eq!(
+                lifting.kth_ancestor(a, k),
+                naive_kth_ancestor(tree, a, k),
+                "ancestor {k} of {a}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_company_queries_example() {
+        // bosses 1 1 3 3 of employees 2..=5
+        let tree = RootedTree::from_parents(vec![NO_PARENT, 0, 0, 2, 2]);
+        let lifting = BinaryLifting::new(&tree);
+        assert_eq!(lifting.kth_ancestor(3, 1), Some(2));
+        assert_eq!(lifting.kth_ancestor(3, 2), Some(0));
+        assert_eq!(lifting.kth_ancestor(3, 3), None);
+        assert_eq!(lifting.kth_ancestor(3, 0), Some(3));
+        assert_eq!(lifting.lca(3, 4), 2);
+        assert_eq!(lifting.lca(1, 4), 0);
+        let euler = EulerTourLca::new(&tree);
+        assert_eq!(
+            (euler.lca(3, 4), euler.lca(1, 4), euler.lca(2, 2)),
+            (2, 0, 2)
+        );
+        assert_eq!(euler.distance(1, 4), 3);
+
+        let single = RootedTree::from_parents(vec![NO_PARENT]);
+        assert_eq!(BinaryLifting::new(&single).lca(0, 0), 0);
+        assert_eq!(EulerTourLca::new(&single).lca(0, 0), 0);
+    }
+
+    #[test]
+    fn test_small_trees_exhaustive() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..200 {
+            let n = rng.gen_range(1..=40);
+            let tree = RootedTree::from_edges(n, &random_edges(&mut rng, n), rng.gen_range(0..n));
+            let queries = (0..n).flat_map(|a| (0..n).map(move |b| (a, b, b)));
+            check(&tree, queries);
+        }
+    }
+
+    #[test]
+    fn test_large_trees() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for n in [200_000, 150_000, 200_000, 100_000] {
+            let tree = RootedTree::from_edges(n, &random_edges(&mut rng, n), rng.gen_range(0..n));
+            let queries: Vec<(usize, usize, usize)> = (0..2000)
+                .map(|_| {
+                    let a = rng.gen_range(0..n);
+                    (a, rng.gen_range(0..n), rng.gen_range(0..=tree.depth(a) + 1))
+                })
+                .collect();
+            check(&tree, queries.into_iter());
+        }
+
+        // a path is the deepest tree, and the worst case for the naive walk
+        let n = 200_000;
+        let edges: Vec<(usize, usize)> = (1..n).map(|v| (v - 1, v)).collect();
+        let tree = RootedTree::from_edges(n, &edges, 0);
+        check(
+            &tree,
+            [(n - 1, n / 2, n - 1), (3, n - 2, n), (n - 1, 0, 1)].into_iter(),
+        );
+    }
+}
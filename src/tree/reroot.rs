@@ -0,0 +1,297 @@
+//! Rerooting: a tree DP evaluated with every node as the root, in O(n) merges.
+//!
+//! The DP of a rooted subtree is `add_root(merge(add_edge(child subtree) for every child), root)`. A first pass computes
+//! it bottom-up from an arbitrary root, a second pass hands every child the value of "the rest of the tree" seen from
+//! its parent, combining the other children with prefix and suffix merges (so `merge` only has to be associative).
+//!
+//! Instances: [`MaxDistance`] (Tree Distances I, and Tree Diameter as the largest one), [`DistanceSum`]
+//! (Tree Distances II) and [`Matching`] (Tree Matching).
+
+use super::RootedTree;
+
+/// The rules of a rerooting DP.
+pub trait Rerooting {
+    /// value of a subtree hanging from its root, and of merged edge contributions
+    type Value: Clone;
+
+    /// neutral element of `merge`
+    fn identity(&self) -> Self::Value;
+    /// combine the contributions of two sets of neighbors, must be associative
+    fn merge(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+    /// contribution of the subtree rooted at `child` to its neighbor `parent`
+    fn add_edge(&self, subtree: &Self::Value, child: usize, parent: usize) -> Self::Value;
+    /// value of the subtree rooted at `v`, from the merged contributions of its children
+    fn add_root(&self, merged: &Self::Value, v: usize) -> Self::Value;
+}
+
+/// The DP value of the whole tree rooted at every node.
+#[must_use]
+pub fn reroot<R: Rerooting>(tree: &RootedTree, rules: &R) -> Vec<R::Value> {
+    let n = tree.len();
+    let order = tree.bfs_order();
+
+    let mut down: Vec<R::Value> = vec![rules.identity(); n];
+    for &v in order.iter().rev() {
+        let merged = tree.children(v).iter().fold(rules.identity(), |acc, &c| {
+            rules.merge(&acc, &rules.add_edge(&down[c], c, v))
+        });
+        down[v] = rules.add_root(&merged, v);
+    }
+
+    // up[v]: the tree without the subtree of v, rooted at the parent of v
+    let mut up: Vec<R::Value> = vec![rules.identity(); n];
+    let mut answer: Vec<R::Value> = vec![rules.identity(); n];
+    let mut suffix: Vec<R::Value> = vec![];
+    for &v in order {
+        let from_parent = tree
+            .parent(v)
+            .map_or_else(|| rules.identity(), |p| rules.add_edge(&up[v], p, v));
+        let children = tree.children(v);
+        let contributions: Vec<R::Value> = children
+            .iter()
+            .map(|&c| rules.add_edge(&down[c], c, v))
+            .collect();
+
+        // suffix[i] merges the contributions of children i.., prefix grows as the children are visited
+        suffix.clear();
+        suffix.resize(children.len() + 1, rules.identity());
+        for i in (0..children.len()).rev() {
+            suffix[i] = rules.merge(&contributions[i], &suffix[i + 1]);
+        }
+        let mut prefix = from_parent;
+        for (i, &c) in children.iter().enumerate() {
+            up[c] = rules.add_root(&rules.merge(&prefix, &suffix[i + 1]), v);
+            prefix = rules.merge(&prefix, &contributions[i]);
+        }
+        answer[v] = rules.add_root(&prefix, v);
+    }
+    answer
+}
+
+/// Distance to the farthest node: the height of the tree rooted there.
+pub struct MaxDistance;
+
+impl Rerooting for MaxDistance {
+    type Value = usize;
+
+    fn identity(&self) -> usize {
+        0
+    }
+
+    fn merge(&self, a: &usize, b: &usize) -> usize {
+        *a.max(b)
+    }
+
+    fn add_edge(&self, height: &usize, _child: usize, _parent: usize) -> usize {
+        height + 1
+    }
+
+    fn add_root(&self, merged: &usize, _v: usize) -> usize {
+        *merged
+    }
+}
+
+/// Sum of the distances to every node, as `(nodes, sum of their distances to the root)`.
+pub struct DistanceSum;
+
+impl Rerooting for DistanceSum {
+    type Value = (u64, u64);
+
+    fn identity(&self) -> (u64, u64) {
+        (0, 0)
+    }
+
+    fn merge(&self, a: &(u64, u64), b: &(u64, u64)) -> (u64, u64) {
+        (a.0 + b.0, a.1 + b.1)
+    }
+
+    fn add_edge(&self, &(size, sum): &(u64, u64), _child: usize, _parent: usize) -> (u64, u64) {
+        // every node of the subtree gets one edge further
+        (size, sum + size)
+    }
+
+    fn add_root(&self, &(size, sum): &(u64, u64), _v: usize) -> (u64, u64) {
+        (size + 1, sum)
+    }
+}
+
+/// Maximum matching of the subtree, and the one leaving the root unmatched.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MatchingValue {
+    /// the root is free to be matched with its parent
+    pub root_free: usize,
+    pub best: usize,
+}
+
+/// Maximum matching, with the root free or not. For merged children, `root_free` is the sum of their best matchings
+/// and `best - root_free` (0 or 1) tells whether a child is left free for the root.
+pub struct Matching;
+
+impl Rerooting for Matching {
+    type Value = MatchingValue;
+
+    fn identity(&self) -> MatchingValue {
+        MatchingValue::default()
+    }
+
+    fn merge(&self, a: &MatchingValue, b: &MatchingValue) -> MatchingValue {
+        MatchingValue {
+            root_free: a.root_free + b.root_free,
+            best: a.root_free + b.root_free + (a.best - a.root_free).max(b.best - b.root_free),
+        }
+    }
+
+    fn add_edge(&self, subtree: &MatchingValue, _child: usize, _parent: usize) -> MatchingValue {
+        // matching the child with the parent gains an edge when the child's best can leave it free
+        let gain = 1 + subtree.root_free - subtree.best;
+        MatchingValue {
+            root_free: subtree.best,
+            best: subtree.best + gain,
+        }
+    }
+
+    fn add_root(&self, merged: &MatchingValue, _v: usize) -> MatchingValue {
+        *merged
+    }
+}
+
+/// Tree Distances I: the distance from every node to the farthest one.
+#[must_use]
+pub fn max_distances(tree: &RootedTree) -> Vec<usize> {
+    reroot(tree, &MaxDistance)
+}
+
+/// Tree Diameter: the largest distance between two nodes.
+#[must_use]
+pub fn diameter(tree: &RootedTree) -> usize {
+    max_distances(tree).into_iter().max().unwrap_or_default()
+}
+
+/// Tree Distances II: the sum of the distances from every node to all the others.
+#[must_use]
+pub fn distance_sums(tree: &RootedTree) -> Vec<u64> {
+    reroot(tree, &DistanceSum)
+        .into_iter()
+        .map(|(_, sum)| sum)
+        .collect()
+}
+
+/// Tree Matching: the maximum number of pairs of adjacent nodes with each node in at most one pair, and for every
+/// node the maximum when it stays unpaired.
+#[must_use]
+pub fn matchings(tree: &RootedTree) -> (usize, Vec<usize>) {
+    let values = reroot(tree, &Matching);
+    let best = values.first().map_or(0, |value| value.best);
+    (best, values.iter().map(|value| value.root_free).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tree::test::random_edges;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    fn naive_distances(n: usize, edges: &[(usize, usize)], from: usize) -> Vec<usize> {
+        let mut distance = vec![usize::MAX; n];
+        distance[from] = 0;
+        let mut queue = vec![from];
+        let mut head = 0;
+        while let Some(&v) = queue.get(head) {
+            head += 1;
+            for &(a, b) in edges {
+                for (x, y) in [(a, b), (b, a)] {
+                    if x == v && distance[y] == usize::MAX {
+                        distance[y] = distance[v] + 1;
+                        queue.push(y);
+                    }
+                }
+            }
+        }
+        distance
+    }
+
+    /// maximum matching over every subset of edges, with and without `free` unmatched
+    fn naive_matching(n: usize, edges: &[(usize, usize)], free: usize) -> (usize, usize) {
+        let (mut best, mut best_free) = (0, 0);
+        for mask in 0_u32..1 << edges.len() {
+            let mut used = vec![false; n];
+            let chosen = edges
+                .iter()
+                .enumerate()
+                .filter(|&(idx, _)| mask >> idx & 1 == 1);
+            let mut valid = true;
+            for (_, &(a, b)) in chosen {
+                valid &= !used[a] && !used[b];
+                used[a] = true;
+                used[b] = true;
+            }
+            if valid {
+                let size = mask.count_ones() as usize;
+                best = best.max(size);
+                if !used[free] {
+                    best_free = best_free.max(size);
+                }
+            }
+        }
+        (best, best_free)
+    }
+
+    #[test]
+    fn test_examples() {
+        // 1-2 1-3 3-4 3-5, shared by Tree Diameter, Tree Distances I/II and Tree Matching
+        let tree = RootedTree::from_edges(5, &[(0, 1), (0, 2), (2, 3), (2, 4)], 0);
+        assert_eq!(diameter(&tree), 3);
+        assert_eq!(max_distances(&tree), [2, 3, 2, 3, 3]);
+        assert_eq!(distance_sums(&tree), [6, 9, 5, 8, 8]);
+        assert_eq!(matchings(&tree), (2, vec![1, 1, 1, 2, 2]));
+
+        let single = RootedTree::from_edges(1, &[], 0);
+        assert_eq!(
+            (
+                diameter(&single),
+                distance_sums(&single),
+                matchings(&single)
+            ),
+            (0, vec![0], (0, vec![0]))
+        );
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = StdRng::seed_from_u64(1);
+        for _ in 0..300 {
+            let n = rng.gen_range(1..=13);
+            let edges = random_edges(&mut rng, n);
+            let tree = RootedTree::from_edges(n, &edges, rng.gen_range(0..n));
+
+            let distances: Vec<Vec<usize>> =
+                (0..n).map(|v| naive_distances(n, &edges, v)).collect();
+            let max: Vec<usize> = distances.iter().map(|d| *d.iter().max().unwrap()).collect();
+            let sums: Vec<u64> = distances
+                .iter()
+                .map(|d| d.iter().sum::<usize>() as u64)
+                .collect();
+            assert_eq!(max_distances(&tree), max);
+            assert_eq!(diameter(&tree), *max.iter().max().unwrap());
+            assert_eq!(distance_sums(&tree), sums);
+
+            let (best, free) = matchings(&tree);
+            for (v, &free) in free.iter().enumerate() {
+                assert_eq!((best, free), naive_matching(n, &edges, v), "{edges:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_deep_path() {
+        let n = 200_000;
+        let edges: Vec<(usize, usize)> = (1..n).map(|v| (v - 1, v)).collect();
+        let tree = RootedTree::from_edges(n, &edges, n / 3);
+        assert_eq!(diameter(&tree), n - 1);
+        let sums = distance_sums(&tree);
+        let end = (n as u64 - 1) * n as u64 / 2;
+        assert_eq!((sums[0], sums[n - 1]), (end, end));
+        assert_eq!(matchings(&tree).0, n / 2);
+    }
+}
@@ -0,0 +1,8 @@
+//! Additional Problems
+
+use super::{Validator, Violation};
+
+pub fn multiplication_table(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=999_999)?;
+    v.ensure(n % 2 == 1, || format!("n = {n} is even"))
+}
@@ -0,0 +1,8 @@
+//! Bitwise Operations
+
+use super::{Validator, Violation};
+
+pub fn counting_bits(v: &mut Validator) -> Result<(), Violation> {
+    v.int_line("n", 1..=1_000_000_000_000_000)?;
+    Ok(())
+}
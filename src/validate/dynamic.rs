@@ -0,0 +1,75 @@
+//! Dynamic Programming
+
+use super::{Validator, Violation};
+
+/// the shared input of the coin problems: n distinct coins and a target sum x
+fn coins(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=100)?;
+    v.space()?;
+    v.int_line("x", 1..=1_000_000)?;
+    v.distinct_ints(n, "c", 1..=1_000_000)?;
+    Ok(())
+}
+
+pub fn array_description(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=100_000)?;
+    v.space()?;
+    let m = v.int_line("m", 1..=100)?;
+    v.ints(n, "x", 0..=m)?;
+    Ok(())
+}
+
+pub fn book_shop(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=1000)?;
+    v.space()?;
+    v.int_line("x", 1..=100_000)?;
+    v.ints(n, "h", 1..=1_000_000)?;
+    v.ints(n, "s", 1..=1_000_000)?;
+    Ok(())
+}
+
+pub fn coin_combinations_1(v: &mut Validator) -> Result<(), Violation> {
+    coins(v)
+}
+
+pub fn coin_combinations_2(v: &mut Validator) -> Result<(), Violation> {
+    coins(v)
+}
+
+pub fn dice_combinations(v: &mut Validator) -> Result<(), Violation> {
+    v.int_line("n", 1..=1_000_000)?;
+    Ok(())
+}
+
+pub fn edit_distance(v: &mut Validator) -> Result<(), Violation> {
+    v.word_line("the first string", 1..=5000, |b| b.is_ascii_uppercase())?;
+    v.word_line("the second string", 1..=5000, |b| b.is_ascii_uppercase())?;
+    Ok(())
+}
+
+pub fn grid_paths(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=1000)?;
+    v.grid(n, n, |b| b == b'.' || b == b'*')?;
+    Ok(())
+}
+
+pub fn increasing_subsequence(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=200_000)?;
+    v.ints(n, "x", 1..=1_000_000_000)?;
+    Ok(())
+}
+
+pub fn minimizing_coins(v: &mut Validator) -> Result<(), Violation> {
+    coins(v)
+}
+
+pub fn money_sums(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=100)?;
+    v.ints(n, "x", 1..=1000)?;
+    Ok(())
+}
+
+pub fn removing_digits(v: &mut Validator) -> Result<(), Violation> {
+    v.int_line("n", 1..=1_000_000)?;
+    Ok(())
+}
@@ -0,0 +1,48 @@
+//! Geometry
+
+use super::{Validator, Violation};
+
+const MAX_COORDINATE: i64 = 1_000_000_000;
+
+/// read the coordinates of a point, the first one of a line has no leading space
+fn point(v: &mut Validator, idx: usize) -> Result<(i64, i64), Violation> {
+    if idx != 1 {
+        v.space()?;
+    }
+    let x = v.int(&format!("x{idx}"), -MAX_COORDINATE..=MAX_COORDINATE)?;
+    v.space()?;
+    let y = v.int(&format!("y{idx}"), -MAX_COORDINATE..=MAX_COORDINATE)?;
+    Ok((x, y))
+}
+
+pub fn line_segment_intersection(v: &mut Validator) -> Result<(), Violation> {
+    let t = v.int_line("t", 1..=100_000)?;
+    for _ in 0..t {
+        let p1 = point(v, 1)?;
+        let p2 = point(v, 2)?;
+        v.ensure(p1 != p2, || {
+            "(x1, y1) and (x2, y2) are the same point".to_string()
+        })?;
+        let p3 = point(v, 3)?;
+        let p4 = point(v, 4)?;
+        v.ensure(p3 != p4, || {
+            "(x3, y3) and (x4, y4) are the same point".to_string()
+        })?;
+        v.newline()?;
+    }
+    Ok(())
+}
+
+pub fn point_location_test(v: &mut Validator) -> Result<(), Violation> {
+    let t = v.int_line("t", 1..=100_000)?;
+    for _ in 0..t {
+        let p1 = point(v, 1)?;
+        let p2 = point(v, 2)?;
+        v.ensure(p1 != p2, || {
+            "(x1, y1) and (x2, y2) are the same point".to_string()
+        })?;
+        point(v, 3)?;
+        v.newline()?;
+    }
+    Ok(())
+}
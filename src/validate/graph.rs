@@ -0,0 +1,96 @@
+//! Graph Algorithms
+
+use std::collections::VecDeque;
+
+use super::{Validator, Violation};
+
+const MAX_N: i64 = 100_000;
+const MAX_M: i64 = 200_000;
+
+/// first line `n m`, then m simple undirected edges
+fn simple_graph(v: &mut Validator, min_n: i64) -> Result<(), Violation> {
+    let n = v.int("n", min_n..=MAX_N)?;
+    v.space()?;
+    let m = v.int_line("m", 1..=MAX_M)?;
+    v.simple_edges(m, n)?;
+    Ok(())
+}
+
+/// first line `n m`, then the n rows of an n * m map
+fn map<'a>(v: &mut Validator<'a>, allowed: &[u8]) -> Result<Vec<&'a [u8]>, Violation> {
+    let n = v.int("n", 1..=1000)?;
+    v.space()?;
+    let m = v.int_line("m", 1..=1000)?;
+    v.grid(n, m, |b| allowed.contains(&b))
+}
+
+pub fn building_roads(v: &mut Validator) -> Result<(), Violation> {
+    simple_graph(v, 1)
+}
+
+pub fn building_teams(v: &mut Validator) -> Result<(), Violation> {
+    simple_graph(v, 1)
+}
+
+pub fn counting_rooms(v: &mut Validator) -> Result<(), Violation> {
+    map(v, b".#")?;
+    Ok(())
+}
+
+pub fn labyrinth(v: &mut Validator) -> Result<(), Violation> {
+    let rows = map(v, b".#AB")?;
+    for cell in [b'A', b'B'] {
+        let count = rows
+            .iter()
+            .flat_map(|row| row.iter())
+            .filter(|&&b| b == cell)
+            .count();
+        v.ensure(count == 1, || {
+            format!(
+                "the map has {count} {}, expected exactly one",
+                char::from(cell)
+            )
+        })?;
+    }
+    Ok(())
+}
+
+pub fn message_route(v: &mut Validator) -> Result<(), Violation> {
+    simple_graph(v, 2)
+}
+
+pub fn round_trip(v: &mut Validator) -> Result<(), Violation> {
+    simple_graph(v, 1)
+}
+
+pub fn shortest_routes_1(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 2..=MAX_N)?;
+    v.space()?;
+    let m = v.int_line("m", 1..=MAX_M)?;
+    let mut flights = vec![vec![]; usize::try_from(n).unwrap_or_default() + 1];
+    for _ in 0..m {
+        let from = v.int("a", 1..=n)?;
+        v.space()?;
+        let to = v.int("b", 1..=n)?;
+        v.space()?;
+        v.int_line("c", 1..=1_000_000_000)?;
+        flights[usize::try_from(from).unwrap_or_default()]
+            .push(usize::try_from(to).unwrap_or_default());
+    }
+
+    let mut seen = vec![false; flights.len()];
+    seen[1] = true;
+    let mut queue = VecDeque::from([1]);
+    while let Some(city) = queue.pop_front() {
+        for &next in &flights[city] {
+            if !seen[next] {
+                seen[next] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    match (1..seen.len()).find(|&city| !seen[city]) {
+        Some(city) => v.fail(format!("city {city} is not reachable from city 1")),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,110 @@
+//! Introductory Problems
+
+use super::{Validator, Violation};
+
+pub fn apple_division(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=20)?;
+    v.ints(n, "p", 1..=1_000_000_000)?;
+    Ok(())
+}
+
+pub fn bit_strings(v: &mut Validator) -> Result<(), Violation> {
+    v.int_line("n", 1..=1_000_000)?;
+    Ok(())
+}
+
+pub fn chessboard_and_queens(v: &mut Validator) -> Result<(), Violation> {
+    v.grid(8, 8, |b| b == b'.' || b == b'*')?;
+    Ok(())
+}
+
+pub fn coin_piles(v: &mut Validator) -> Result<(), Violation> {
+    let t = v.int_line("t", 1..=100_000)?;
+    for _ in 0..t {
+        v.ints(2, "a, b", 0..=1_000_000_000)?;
+    }
+    Ok(())
+}
+
+pub fn creating_strings(v: &mut Validator) -> Result<(), Violation> {
+    v.word_line("the string", 1..=8, |b| b.is_ascii_lowercase())?;
+    Ok(())
+}
+
+pub fn digit_queries(v: &mut Validator) -> Result<(), Violation> {
+    let q = v.int_line("q", 1..=1000)?;
+    for _ in 0..q {
+        v.int_line("k", 1..=1_000_000_000_000_000_000)?;
+    }
+    Ok(())
+}
+
+pub fn gray_code(v: &mut Validator) -> Result<(), Violation> {
+    v.int_line("n", 1..=16)?;
+    Ok(())
+}
+
+pub fn grid_paths(v: &mut Validator) -> Result<(), Violation> {
+    v.word_line("the description", 48..=48, |b| b"?DULR".contains(&b))?;
+    Ok(())
+}
+
+pub fn increasing_array(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=200_000)?;
+    v.ints(n, "x", 1..=1_000_000_000)?;
+    Ok(())
+}
+
+pub fn missing_number(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 2..=200_000)?;
+    v.distinct_ints(n - 1, "x", 1..=n)?;
+    Ok(())
+}
+
+pub fn number_spiral(v: &mut Validator) -> Result<(), Violation> {
+    let t = v.int_line("t", 1..=100_000)?;
+    for _ in 0..t {
+        v.ints(2, "y, x", 1..=1_000_000_000)?;
+    }
+    Ok(())
+}
+
+pub fn palindrome_reorder(v: &mut Validator) -> Result<(), Violation> {
+    v.word_line("the string", 1..=1_000_000, |b| b.is_ascii_uppercase())?;
+    Ok(())
+}
+
+pub fn permutations(v: &mut Validator) -> Result<(), Violation> {
+    v.int_line("n", 1..=1_000_000)?;
+    Ok(())
+}
+
+pub fn repetitions(v: &mut Validator) -> Result<(), Violation> {
+    v.word_line("the sequence", 1..=1_000_000, |b| b"ACGT".contains(&b))?;
+    Ok(())
+}
+
+pub fn tower_of_hanoi(v: &mut Validator) -> Result<(), Violation> {
+    v.int_line("n", 1..=16)?;
+    Ok(())
+}
+
+pub fn trailing_zeros(v: &mut Validator) -> Result<(), Violation> {
+    v.int_line("n", 1..=1_000_000_000)?;
+    Ok(())
+}
+
+pub fn two_knights(v: &mut Validator) -> Result<(), Violation> {
+    v.int_line("n", 1..=10_000)?;
+    Ok(())
+}
+
+pub fn two_sets(v: &mut Validator) -> Result<(), Violation> {
+    v.int_line("n", 1..=1_000_000)?;
+    Ok(())
+}
+
+pub fn weird_algorithm(v: &mut Validator) -> Result<(), Violation> {
+    v.int_line("n", 1..=1_000_000)?;
+    Ok(())
+}
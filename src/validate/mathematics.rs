@@ -0,0 +1,130 @@
+//! Mathematics
+
+use super::{Validator, Violation};
+
+const MAX_EXPONENT: i64 = 1_000_000_000_000_000_000;
+
+/// deterministic Miller-Rabin, the first 12 primes as witnesses are enough below 2^64
+fn is_prime(n: u64) -> bool {
+    const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    let mul = |a: u64, b: u64| {
+        u64::try_from(u128::from(a) * u128::from(b) % u128::from(n)).unwrap_or_default()
+    };
+    let pow = |mut base: u64, mut exponent: u64| {
+        let mut result = 1;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = mul(result, base);
+            }
+            base = mul(base, base);
+            exponent >>= 1;
+        }
+        result
+    };
+
+    if n < 2 {
+        return false;
+    }
+    if let Some(&p) = WITNESSES.iter().find(|&&p| n % p == 0) {
+        return n == p;
+    }
+    let shift = (n - 1).trailing_zeros();
+    let odd = (n - 1) >> shift;
+    WITNESSES.iter().all(|&witness| {
+        let mut x = pow(witness, odd);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..shift {
+            x = mul(x, x);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+pub fn counting_divisors(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=100_000)?;
+    for _ in 0..n {
+        v.int_line("x", 1..=1_000_000)?;
+    }
+    Ok(())
+}
+
+pub fn exponentiation(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=200_000)?;
+    for _ in 0..n {
+        v.ints(2, "a, b", 0..=1_000_000_000)?;
+    }
+    Ok(())
+}
+
+pub fn exponentiation_2(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=100_000)?;
+    for _ in 0..n {
+        v.ints(3, "a, b, c", 0..=1_000_000_000)?;
+    }
+    Ok(())
+}
+
+pub fn fibonacci_numbers(v: &mut Validator) -> Result<(), Violation> {
+    v.int_line("n", 0..=MAX_EXPONENT)?;
+    Ok(())
+}
+
+pub fn josephus_queries(v: &mut Validator) -> Result<(), Violation> {
+    let q = v.int_line("q", 1..=100_000)?;
+    for _ in 0..q {
+        let n = v.int("n", 1..=1_000_000_000)?;
+        v.space()?;
+        v.int_line("k", 1..=n)?;
+    }
+    Ok(())
+}
+
+pub fn prime_multiples(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=MAX_EXPONENT)?;
+    v.space()?;
+    let k = v.int_line("k", 1..=20)?;
+    let mut seen = Vec::new();
+    for idx in 0..k {
+        if idx != 0 {
+            v.space()?;
+        }
+        let a = v.int("a", 2..=n)?;
+        v.ensure(is_prime(a.unsigned_abs()), || {
+            format!("a = {a} is not prime")
+        })?;
+        v.ensure(!seen.contains(&a), || {
+            format!("a = {a} appears more than once")
+        })?;
+        seen.push(a);
+    }
+    v.newline()?;
+    Ok(())
+}
+
+pub fn throwing_dice(v: &mut Validator) -> Result<(), Violation> {
+    v.int_line("n", 1..=MAX_EXPONENT)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_prime() {
+        let naive = |n: u64| n >= 2 && (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0);
+        for n in 0..10_000 {
+            assert_eq!(is_prime(n), naive(n), "{n}");
+        }
+        assert!(is_prime(1_000_000_007));
+        assert!(is_prime(999_999_999_999_999_989));
+        // strong pseudoprime to the bases 2, 3, 5 and 7
+        assert!(!is_prime(3_215_031_751));
+    }
+}
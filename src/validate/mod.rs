@@ -0,0 +1,589 @@
+//! Input validators enforcing each problem's documented format and constraints.
+//!
+//! The solutions parse their input optimistically (unchecked indexing, `unwrap_unchecked`, no ASCII checks), so an
+//! input which breaks the constraints is undefined behavior instead of a clean failure. Every hand-written test runs
+//! its input through [`assert_valid`] first, and so should anything which generates inputs.
+//!
+//! Validation is strict: tokens are separated by exactly one space, every line (including the last one) ends with
+//! `\n`, and integers have no sign prefix other than `-` and no leading zeros. The interactive problem has no
+//! validator, its input is produced by the interactor.
+
+mod additional;
+mod bitwise;
+mod dynamic;
+mod geometry;
+mod graph;
+mod intro;
+mod mathematics;
+mod range;
+mod search;
+mod sliding_window;
+mod string;
+mod tree;
+
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// First place where an input breaks its problem's format or constraints. Line and column are 1-indexed bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for Violation {}
+
+pub type Check = fn(&mut Validator) -> Result<(), Violation>;
+
+/// Strict tokenizer over an input, every read either returns the expected token or the position where it went wrong.
+pub struct Validator<'a> {
+    input: &'a [u8],
+    position: usize,
+    /// start of the last token read, where constraint violations are reported
+    token_start: usize,
+}
+
+impl<'a> Validator<'a> {
+    #[must_use]
+    pub fn new(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            position: 0,
+            token_start: 0,
+        }
+    }
+
+    fn violation_at(&self, position: usize, message: String) -> Violation {
+        let before = &self.input[..position];
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |idx| idx + 1);
+        Violation {
+            line: before.split(|&b| b == b'\n').count(),
+            column: position - line_start + 1,
+            message,
+        }
+    }
+
+    /// fail at the start of the last token read
+    ///
+    /// # Errors
+    ///   always
+    pub fn fail<T>(&self, message: impl Into<String>) -> Result<T, Violation> {
+        Err(self.violation_at(self.token_start, message.into()))
+    }
+
+    /// fail at the start of the last token read, unless `condition` holds
+    ///
+    /// # Errors
+    ///   if `condition` is false
+    pub fn ensure(
+        &self,
+        condition: bool,
+        message: impl FnOnce() -> String,
+    ) -> Result<(), Violation> {
+        if condition {
+            Ok(())
+        } else {
+            self.fail(message())
+        }
+    }
+
+    fn describe(byte: Option<&u8>) -> String {
+        match byte {
+            None => "end of input".to_string(),
+            Some(b'\n') => "a newline".to_string(),
+            Some(b'\r') => "a carriage return".to_string(),
+            Some(b' ') => "a space".to_string(),
+            Some(&byte) => format!("'{}'", byte.escape_ascii()),
+        }
+    }
+
+    fn expect(&mut self, byte: u8, what: &str) -> Result<(), Violation> {
+        let found = self.input.get(self.position);
+        if found == Some(&byte) {
+            self.position += 1;
+            Ok(())
+        } else {
+            Err(self.violation_at(
+                self.position,
+                format!("expected {what}, found {}", Self::describe(found)),
+            ))
+        }
+    }
+
+    /// # Errors
+    ///   if the next byte is not a single space
+    pub fn space(&mut self) -> Result<(), Violation> {
+        self.expect(b' ', "a space")
+    }
+
+    /// # Errors
+    ///   if the next byte is not `\n`
+    pub fn newline(&mut self) -> Result<(), Violation> {
+        self.expect(b'\n', "a newline")
+    }
+
+    /// # Errors
+    ///   if there is anything left in the input
+    pub fn eof(&mut self) -> Result<(), Violation> {
+        match self.input.get(self.position) {
+            None => Ok(()),
+            found => Err(self.violation_at(
+                self.position,
+                format!("expected end of input, found {}", Self::describe(found)),
+            )),
+        }
+    }
+
+    /// raw bytes up to the next whitespace or end of input, the separator is checked by the caller
+    fn token(&mut self) -> &'a [u8] {
+        self.token_start = self.position;
+        let len = self.input[self.position..]
+            .iter()
+            .position(u8::is_ascii_whitespace)
+            .unwrap_or(self.input.len() - self.position);
+        self.position += len;
+        &self.input[self.token_start..self.position]
+    }
+
+    /// Read an integer within `range`, `name` is the variable name from the statement.
+    ///
+    /// # Errors
+    ///   if the token is not a canonical integer, or is out of range
+    pub fn int(&mut self, name: &str, range: RangeInclusive<i64>) -> Result<i64, Violation> {
+        let token = self.token();
+        let digits = token.strip_prefix(b"-").unwrap_or(token);
+        if let Some(offset) = digits.iter().position(|b| !b.is_ascii_digit()) {
+            let position = self.token_start + token.len() - digits.len() + offset;
+            return Err(self.violation_at(
+                position,
+                format!(
+                    "expected a digit in {name}, found {}",
+                    Self::describe(digits.get(offset))
+                ),
+            ));
+        }
+        if digits.is_empty() {
+            return self.fail(format!(
+                "expected integer {name}, found {}",
+                Self::describe(self.input.get(self.position))
+            ));
+        }
+        if (digits.len() > 1 && digits[0] == b'0') || token == b"-0" {
+            return self.fail(format!("{name} has a leading zero"));
+        }
+
+        let value = std::str::from_utf8(token)
+            .ok()
+            .and_then(|t| t.parse::<i64>().ok());
+        match value {
+            Some(value) if range.contains(&value) => Ok(value),
+            _ => self.fail(format!(
+                "{name} = {} is out of range {}..={}",
+                String::from_utf8_lossy(token),
+                range.start(),
+                range.end()
+            )),
+        }
+    }
+
+    /// Read an integer followed by a newline.
+    ///
+    /// # Errors
+    ///   see [`Validator::int`] and [`Validator::newline`]
+    pub fn int_line(&mut self, name: &str, range: RangeInclusive<i64>) -> Result<i64, Violation> {
+        let value = self.int(name, range)?;
+        self.newline()?;
+        Ok(value)
+    }
+
+    /// Read a line of `count` space-separated integers.
+    ///
+    /// # Errors
+    ///   see [`Validator::int`], [`Validator::space`] and [`Validator::newline`]
+    pub fn ints(
+        &mut self,
+        count: i64,
+        name: &str,
+        range: RangeInclusive<i64>,
+    ) -> Result<Vec<i64>, Violation> {
+        let mut values = Vec::with_capacity(usize::try_from(count).unwrap_or_default());
+        for idx in 0..count {
+            if idx != 0 {
+                self.space()?;
+            }
+            values.push(self.int(name, range.clone())?);
+        }
+        self.newline()?;
+        Ok(values)
+    }
+
+    /// Read a line of `count` space-separated pairwise distinct integers.
+    ///
+    /// # Errors
+    ///   see [`Validator::ints`], or if a value is repeated
+    pub fn distinct_ints(
+        &mut self,
+        count: i64,
+        name: &str,
+        range: RangeInclusive<i64>,
+    ) -> Result<Vec<i64>, Violation> {
+        let mut seen = HashSet::new();
+        let mut values = Vec::with_capacity(usize::try_from(count).unwrap_or_default());
+        for idx in 0..count {
+            if idx != 0 {
+                self.space()?;
+            }
+            let value = self.int(name, range.clone())?;
+            self.ensure(seen.insert(value), || {
+                format!("{name} = {value} appears more than once")
+            })?;
+            values.push(value);
+        }
+        self.newline()?;
+        Ok(values)
+    }
+
+    /// Read a line with a permutation of `1..=n`.
+    ///
+    /// # Errors
+    ///   see [`Validator::distinct_ints`]
+    pub fn permutation(&mut self, n: i64, name: &str) -> Result<Vec<i64>, Violation> {
+        self.distinct_ints(n, name, 1..=n)
+    }
+
+    /// Read a token whose length is within `lengths` and whose characters all satisfy `allowed`.
+    ///
+    /// # Errors
+    ///   if the token has the wrong length or a forbidden character
+    pub fn word(
+        &mut self,
+        name: &str,
+        lengths: RangeInclusive<usize>,
+        allowed: impl Fn(u8) -> bool,
+    ) -> Result<&'a [u8], Violation> {
+        let token = self.token();
+        if let Some(offset) = token.iter().position(|&b| !allowed(b)) {
+            return Err(self.violation_at(
+                self.token_start + offset,
+                format!(
+                    "unexpected character {} in {name}",
+                    Self::describe(token.get(offset))
+                ),
+            ));
+        }
+        self.ensure(lengths.contains(&token.len()), || {
+            format!(
+                "{name} has length {}, expected {}..={}",
+                token.len(),
+                lengths.start(),
+                lengths.end()
+            )
+        })?;
+        Ok(token)
+    }
+
+    /// Read a word followed by a newline.
+    ///
+    /// # Errors
+    ///   see [`Validator::word`] and [`Validator::newline`]
+    pub fn word_line(
+        &mut self,
+        name: &str,
+        lengths: RangeInclusive<usize>,
+        allowed: impl Fn(u8) -> bool,
+    ) -> Result<&'a [u8], Violation> {
+        let word = self.word(name, lengths, allowed)?;
+        self.newline()?;
+        Ok(word)
+    }
+
+    /// Read a grid of `rows` lines with `columns` characters each.
+    ///
+    /// # Errors
+    ///   see [`Validator::word_line`]
+    pub fn grid(
+        &mut self,
+        rows: i64,
+        columns: i64,
+        allowed: impl Fn(u8) -> bool + Copy,
+    ) -> Result<Vec<&'a [u8]>, Violation> {
+        let columns = usize::try_from(columns).unwrap_or_default();
+        (0..rows)
+            .map(|_| self.word_line("grid row", columns..=columns, allowed))
+            .collect()
+    }
+
+    /// Read `m` lines of undirected edges `a b` between nodes `1..=n`, rejecting self-loops and repeated edges.
+    ///
+    /// # Errors
+    ///   if an edge is malformed, a self-loop, or repeated
+    pub fn simple_edges(&mut self, m: i64, n: i64) -> Result<Vec<(i64, i64)>, Violation> {
+        let mut seen = HashSet::new();
+        (0..m)
+            .map(|_| {
+                let a = self.int("a", 1..=n)?;
+                self.space()?;
+                let b = self.int("b", 1..=n)?;
+                self.ensure(a != b, || format!("edge {a} {b} is a self-loop"))?;
+                self.ensure(seen.insert((a.min(b), a.max(b))), || {
+                    format!("edge {a} {b} appears more than once")
+                })?;
+                self.newline()?;
+                Ok((a, b))
+            })
+            .collect()
+    }
+}
+
+/// A+B, from the template
+fn template(v: &mut Validator) -> Result<(), Violation> {
+    v.int("A", -1_000_000..=1_000_000)?;
+    v.space()?;
+    v.int_line("B", -1_000_000..=1_000_000)?;
+    Ok(())
+}
+
+/// bin names are `<CATEGORY>_<PROBLEM_NAME>`, each validator is `<CATEGORY>::<PROBLEM_NAME>`
+macro_rules! registry {
+    ($($category:ident::$problem:ident),+ $(,)?) => {
+        /// Find the validator of a bin.
+        #[must_use]
+        pub fn validator(bin: &str) -> Option<Check> {
+            match bin {
+                "0_cses_template" => Some(template),
+                $(concat!(stringify!($category), "_", stringify!($problem)) => Some($category::$problem),)+
+                _ => None,
+            }
+        }
+
+        /// Names of every bin which has a validator.
+        pub const VALIDATED_BINS: &[&str] = &[
+            "0_cses_template",
+            $(concat!(stringify!($category), "_", stringify!($problem)),)+
+        ];
+    };
+}
+
+registry! {
+    additional::multiplication_table,
+    bitwise::counting_bits,
+    dynamic::array_description,
+    dynamic::book_shop,
+    dynamic::coin_combinations_1,
+    dynamic::coin_combinations_2,
+    dynamic::dice_combinations,
+    dynamic::edit_distance,
+    dynamic::grid_paths,
+    dynamic::increasing_subsequence,
+    dynamic::minimizing_coins,
+    dynamic::money_sums,
+    dynamic::removing_digits,
+    geometry::line_segment_intersection,
+    geometry::point_location_test,
+    graph::building_roads,
+    graph::building_teams,
+    graph::counting_rooms,
+    graph::labyrinth,
+    graph::message_route,
+    graph::round_trip,
+    graph::shortest_routes_1,
+    intro::apple_division,
+    intro::bit_strings,
+    intro::chessboard_and_queens,
+    intro::coin_piles,
+    intro::creating_strings,
+    intro::digit_queries,
+    intro::gray_code,
+    intro::grid_paths,
+    intro::increasing_array,
+    intro::missing_number,
+    intro::number_spiral,
+    intro::palindrome_reorder,
+    intro::permutations,
+    intro::repetitions,
+    intro::tower_of_hanoi,
+    intro::trailing_zeros,
+    intro::two_knights,
+    intro::two_sets,
+    intro::weird_algorithm,
+    mathematics::counting_divisors,
+    mathematics::exponentiation,
+    mathematics::exponentiation_2,
+    mathematics::fibonacci_numbers,
+    mathematics::josephus_queries,
+    mathematics::prime_multiples,
+    mathematics::throwing_dice,
+    range::forest_queries,
+    range::range_xor_queries,
+    range::static_range_sum_queries,
+    search::apartments,
+    search::array_division,
+    search::collecting_numbers,
+    search::collecting_numbers_2,
+    search::concert_tickets,
+    search::distinct_numbers,
+    search::factory_machines,
+    search::ferris_wheel,
+    search::josephus_problem_1,
+    search::josephus_problem_2,
+    search::maximum_subarray_sum,
+    search::missing_coin_sum,
+    search::movie_festival,
+    search::nearest_smaller_values,
+    search::playlist,
+    search::reading_books,
+    search::restaurant_customers,
+    search::room_allocation,
+    search::stick_lengths,
+    search::subarray_divisibility,
+    search::subarray_sums_1,
+    search::subarray_sums_2,
+    search::sum_of_three_values,
+    search::sum_of_two_values,
+    search::tasks_and_deadlines,
+    search::towers,
+    search::traffic_lights,
+    sliding_window::median,
+    string::finding_borders,
+    string::minimal_rotation,
+    string::string_matching,
+    tree::subordinates,
+}
+
+/// Validate an input for a bin, including that nothing follows the expected input.
+///
+/// # Errors
+///   the first violation found
+///
+/// # Panics
+///   if `bin` has no validator
+pub fn validate(bin: &str, input: &[u8]) -> Result<(), Violation> {
+    let check = validator(bin).unwrap_or_else(|| panic!("no validator for bin '{bin}'"));
+    let mut validator = Validator::new(input);
+    check(&mut validator)?;
+    validator.eof()
+}
+
+/// Panic with the first violation, meant for tests and generators.
+///
+/// # Panics
+///   if the input is invalid, or `bin` has no validator
+pub fn assert_valid(bin: &str, input: &[u8]) {
+    if let Err(violation) = validate(bin, input) {
+        panic!("invalid input for {bin} at {violation}");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn violation(bin: &str, input: &[u8]) -> String {
+        validate(bin, input).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_every_bin_has_a_validator() {
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/bin");
+        for entry in std::fs::read_dir(root).unwrap() {
+            let path = entry.unwrap().path();
+            let bin = path.file_stem().unwrap().to_str().unwrap();
+            if !bin.starts_with("interactive_") {
+                assert!(validator(bin).is_some(), "{bin} has no validator");
+            }
+        }
+    }
+
+    #[test]
+    fn test_format() {
+        assert_eq!(validate("0_cses_template", b"3 5\n"), Ok(()));
+        assert_eq!(
+            violation("0_cses_template", b"3  5\n"),
+            "1:3: expected integer B, found a space"
+        );
+        assert_eq!(
+            violation("0_cses_template", b"3 5"),
+            "1:4: expected a newline, found end of input"
+        );
+        assert_eq!(
+            violation("0_cses_template", b"3 5\r\n"),
+            "1:4: expected a newline, found a carriage return"
+        );
+        assert_eq!(
+            violation("0_cses_template", b"3 5\n\n"),
+            "2:1: expected end of input, found a newline"
+        );
+        assert_eq!(
+            violation("0_cses_template", b"3\n5\n"),
+            "1:2: expected a space, found a newline"
+        );
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(validate("0_cses_template", b"-1000000 0\n"), Ok(()));
+        assert_eq!(
+            violation("0_cses_template", b"-1000001 0\n"),
+            "1:1: A = -1000001 is out of range -1000000..=1000000"
+        );
+        assert_eq!(
+            violation("0_cses_template", b"3 05\n"),
+            "1:3: B has a leading zero"
+        );
+        assert_eq!(
+            violation("0_cses_template", b"-0 5\n"),
+            "1:1: A has a leading zero"
+        );
+        assert_eq!(
+            violation("0_cses_template", b"+3 5\n"),
+            "1:1: expected a digit in A, found '+'"
+        );
+        assert_eq!(
+            violation("0_cses_template", b"3 5x\n"),
+            "1:4: expected a digit in B, found 'x'"
+        );
+        assert_eq!(
+            violation("0_cses_template", b"3 99999999999999999999\n"),
+            "1:3: B = 99999999999999999999 is out of range -1000000..=1000000"
+        );
+    }
+
+    #[test]
+    fn test_constraints_point_at_the_token() {
+        assert_eq!(
+            violation("search_traffic_lights", b"8 3\n3 6 3\n"),
+            "2:5: p = 3 appears more than once"
+        );
+        assert_eq!(
+            violation("search_collecting_numbers", b"3\n1 4 2\n"),
+            "2:3: x = 4 is out of range 1..=3"
+        );
+        assert_eq!(
+            violation("graph_building_roads", b"3 2\n1 2\n2 1\n"),
+            "3:3: edge 2 1 appears more than once"
+        );
+        assert_eq!(
+            violation("intro_repetitions", b"ACGTX\n"),
+            "1:5: unexpected character 'X' in the sequence"
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid input for intro_weird_algorithm at 1:1: n = 0 is out of range 1..=1000000"
+    )]
+    fn test_assert_valid() {
+        assert_valid("intro_weird_algorithm", b"0\n");
+    }
+}
@@ -0,0 +1,42 @@
+//! Range Queries
+
+use super::{Validator, Violation};
+
+/// first line `n q`, an array of n values in 1..=10^9, then q ranges `a b` with `1 ≤ a ≤ b ≤ n`
+fn static_array_queries(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=200_000)?;
+    v.space()?;
+    let q = v.int_line("q", 1..=200_000)?;
+    v.ints(n, "x", 1..=1_000_000_000)?;
+    for _ in 0..q {
+        let a = v.int("a", 1..=n)?;
+        v.space()?;
+        v.int_line("b", a..=n)?;
+    }
+    Ok(())
+}
+
+pub fn forest_queries(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=1000)?;
+    v.space()?;
+    let q = v.int_line("q", 1..=200_000)?;
+    v.grid(n, n, |b| b == b'.' || b == b'*')?;
+    for _ in 0..q {
+        let y1 = v.int("y1", 1..=n)?;
+        v.space()?;
+        let x1 = v.int("x1", 1..=n)?;
+        v.space()?;
+        v.int("y2", y1..=n)?;
+        v.space()?;
+        v.int_line("x2", x1..=n)?;
+    }
+    Ok(())
+}
+
+pub fn range_xor_queries(v: &mut Validator) -> Result<(), Violation> {
+    static_array_queries(v)
+}
+
+pub fn static_range_sum_queries(v: &mut Validator) -> Result<(), Violation> {
+    static_array_queries(v)
+}
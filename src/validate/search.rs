@@ -0,0 +1,219 @@
+//! Sorting and Searching
+
+use std::collections::HashSet;
+
+use super::{Validator, Violation};
+
+const MAX_N: i64 = 200_000;
+const MAX_VALUE: i64 = 1_000_000_000;
+
+/// a first line with only n, then a line of n values
+fn array(
+    v: &mut Validator,
+    name: &str,
+    values: std::ops::RangeInclusive<i64>,
+) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=MAX_N)?;
+    v.ints(n, name, values)?;
+    Ok(())
+}
+
+/// n lines of intervals `a b` with `a ≤ b`
+fn intervals(v: &mut Validator, n: i64) -> Result<Vec<(i64, i64)>, Violation> {
+    (0..n)
+        .map(|_| {
+            let a = v.int("a", 1..=MAX_VALUE)?;
+            v.space()?;
+            let b = v.int("b", a..=MAX_VALUE)?;
+            v.newline()?;
+            Ok((a, b))
+        })
+        .collect()
+}
+
+pub fn apartments(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=MAX_N)?;
+    v.space()?;
+    let m = v.int("m", 1..=MAX_N)?;
+    v.space()?;
+    v.int_line("k", 0..=MAX_VALUE)?;
+    v.ints(n, "a", 1..=MAX_VALUE)?;
+    v.ints(m, "b", 1..=MAX_VALUE)?;
+    Ok(())
+}
+
+pub fn array_division(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=MAX_N)?;
+    v.space()?;
+    v.int_line("k", 1..=n)?;
+    v.ints(n, "x", 1..=MAX_VALUE)?;
+    Ok(())
+}
+
+pub fn collecting_numbers(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=MAX_N)?;
+    v.permutation(n, "x")?;
+    Ok(())
+}
+
+pub fn collecting_numbers_2(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=MAX_N)?;
+    v.space()?;
+    let m = v.int_line("m", 1..=MAX_N)?;
+    v.permutation(n, "x")?;
+    for _ in 0..m {
+        v.ints(2, "a, b", 1..=n)?;
+    }
+    Ok(())
+}
+
+pub fn concert_tickets(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=MAX_N)?;
+    v.space()?;
+    let m = v.int_line("m", 1..=MAX_N)?;
+    v.ints(n, "h", 1..=MAX_VALUE)?;
+    v.ints(m, "t", 1..=MAX_VALUE)?;
+    Ok(())
+}
+
+pub fn distinct_numbers(v: &mut Validator) -> Result<(), Violation> {
+    array(v, "x", 1..=MAX_VALUE)
+}
+
+pub fn factory_machines(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=MAX_N)?;
+    v.space()?;
+    v.int_line("t", 1..=MAX_VALUE)?;
+    v.ints(n, "k", 1..=MAX_VALUE)?;
+    Ok(())
+}
+
+pub fn ferris_wheel(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=MAX_N)?;
+    v.space()?;
+    let x = v.int_line("x", 1..=MAX_VALUE)?;
+    v.ints(n, "p", 1..=x)?;
+    Ok(())
+}
+
+pub fn josephus_problem_1(v: &mut Validator) -> Result<(), Violation> {
+    v.int_line("n", 1..=MAX_N)?;
+    Ok(())
+}
+
+pub fn josephus_problem_2(v: &mut Validator) -> Result<(), Violation> {
+    v.int("n", 1..=MAX_N)?;
+    v.space()?;
+    v.int_line("k", 0..=MAX_VALUE)?;
+    Ok(())
+}
+
+pub fn maximum_subarray_sum(v: &mut Validator) -> Result<(), Violation> {
+    array(v, "x", -MAX_VALUE..=MAX_VALUE)
+}
+
+pub fn missing_coin_sum(v: &mut Validator) -> Result<(), Violation> {
+    array(v, "x", 1..=MAX_VALUE)
+}
+
+pub fn movie_festival(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=MAX_N)?;
+    intervals(v, n)?;
+    Ok(())
+}
+
+pub fn nearest_smaller_values(v: &mut Validator) -> Result<(), Violation> {
+    array(v, "x", 1..=MAX_VALUE)
+}
+
+pub fn playlist(v: &mut Validator) -> Result<(), Violation> {
+    array(v, "k", 1..=MAX_VALUE)
+}
+
+pub fn reading_books(v: &mut Validator) -> Result<(), Violation> {
+    array(v, "t", 1..=MAX_VALUE)
+}
+
+pub fn restaurant_customers(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=MAX_N)?;
+    let mut times = HashSet::new();
+    for _ in 0..n {
+        let a = v.int("a", 1..=MAX_VALUE)?;
+        v.ensure(times.insert(a), || {
+            format!("time {a} appears more than once")
+        })?;
+        v.space()?;
+        let b = v.int("b", a..=MAX_VALUE)?;
+        v.ensure(times.insert(b), || {
+            format!("time {b} appears more than once")
+        })?;
+        v.newline()?;
+    }
+    Ok(())
+}
+
+pub fn room_allocation(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=MAX_N)?;
+    intervals(v, n)?;
+    Ok(())
+}
+
+pub fn stick_lengths(v: &mut Validator) -> Result<(), Violation> {
+    array(v, "p", 1..=MAX_VALUE)
+}
+
+pub fn subarray_divisibility(v: &mut Validator) -> Result<(), Violation> {
+    array(v, "a", -MAX_VALUE..=MAX_VALUE)
+}
+
+pub fn subarray_sums_1(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=MAX_N)?;
+    v.space()?;
+    v.int_line("x", 1..=MAX_VALUE)?;
+    v.ints(n, "a", 1..=MAX_VALUE)?;
+    Ok(())
+}
+
+pub fn subarray_sums_2(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=MAX_N)?;
+    v.space()?;
+    v.int_line("x", -MAX_VALUE..=MAX_VALUE)?;
+    v.ints(n, "a", -MAX_VALUE..=MAX_VALUE)?;
+    Ok(())
+}
+
+pub fn sum_of_three_values(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=5000)?;
+    v.space()?;
+    v.int_line("x", 1..=MAX_VALUE)?;
+    v.ints(n, "a", 1..=MAX_VALUE)?;
+    Ok(())
+}
+
+pub fn sum_of_two_values(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=MAX_N)?;
+    v.space()?;
+    v.int_line("x", 1..=MAX_VALUE)?;
+    v.ints(n, "a", 1..=MAX_VALUE)?;
+    Ok(())
+}
+
+pub fn tasks_and_deadlines(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=MAX_N)?;
+    for _ in 0..n {
+        v.ints(2, "a, d", 1..=1_000_000)?;
+    }
+    Ok(())
+}
+
+pub fn towers(v: &mut Validator) -> Result<(), Violation> {
+    array(v, "k", 1..=MAX_VALUE)
+}
+
+pub fn traffic_lights(v: &mut Validator) -> Result<(), Violation> {
+    let x = v.int("x", 1..=MAX_VALUE)?;
+    v.space()?;
+    let n = v.int_line("n", 1..=MAX_N)?;
+    v.distinct_ints(n, "p", 1..=x - 1)?;
+    Ok(())
+}
@@ -0,0 +1,11 @@
+//! Sliding Window Problems
+
+use super::{Validator, Violation};
+
+pub fn median(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int("n", 1..=200_000)?;
+    v.space()?;
+    v.int_line("k", 1..=n)?;
+    v.ints(n, "x", 1..=1_000_000_000)?;
+    Ok(())
+}
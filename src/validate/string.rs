@@ -0,0 +1,19 @@
+//! String Algorithms
+
+use super::{Validator, Violation};
+
+pub fn finding_borders(v: &mut Validator) -> Result<(), Violation> {
+    v.word_line("the string", 1..=1_000_000, |b| b.is_ascii_lowercase())?;
+    Ok(())
+}
+
+pub fn minimal_rotation(v: &mut Validator) -> Result<(), Violation> {
+    v.word_line("the string", 1..=1_000_000, |b| b.is_ascii_lowercase())?;
+    Ok(())
+}
+
+pub fn string_matching(v: &mut Validator) -> Result<(), Violation> {
+    v.word_line("the string", 1..=1_000_000, |b| b.is_ascii_lowercase())?;
+    v.word_line("the pattern", 1..=1_000_000, |b| b.is_ascii_lowercase())?;
+    Ok(())
+}
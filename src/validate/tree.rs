@@ -0,0 +1,24 @@
+//! Tree Algorithms
+
+use super::{Validator, Violation};
+
+pub fn subordinates(v: &mut Validator) -> Result<(), Violation> {
+    let n = v.int_line("n", 1..=200_000)?;
+    let bosses = v.ints(n - 1, "boss", 1..=n)?;
+
+    // employees 2..=n point to their boss, everyone has to end up under employee 1
+    let n = usize::try_from(n).unwrap_or_default();
+    let mut children = vec![vec![]; n + 1];
+    for (employee, &boss) in (2..).zip(&bosses) {
+        children[usize::try_from(boss).unwrap_or_default()].push(employee);
+    }
+    let mut reached = 0;
+    let mut stack = vec![1];
+    while let Some(employee) = stack.pop() {
+        reached += 1;
+        stack.extend(&children[employee]);
+    }
+    v.ensure(reached == n, || {
+        format!("only {reached} of the {n} employees are under employee 1, the bosses form a cycle")
+    })
+}
@@ -0,0 +1,364 @@
+//! Minimal zip archive reader, enough for the test case archives CSES lets you download.
+//!
+//! Supports stored and deflate entries, which covers every archive produced by common tools.
+//! Zip64, encryption and multi-disk archives are rejected.
+
+mod inflate;
+
+pub use inflate::inflate;
+
+use std::io;
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+/// fixed part of the end of central directory record, which may be followed by a comment of up to 65535 bytes
+const END_OF_CENTRAL_DIRECTORY_SIZE: usize = 22;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATE: u16 = 8;
+
+fn invalid(message: impl Into<String>) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("zip: {}", message.into()),
+    )
+}
+
+/// An entry of the central directory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// full path inside the archive, directories end with `/`
+    pub name: String,
+    method: u16,
+    flags: u16,
+    crc32: u32,
+    compressed_size: usize,
+    size: usize,
+    local_header_offset: usize,
+}
+
+impl Entry {
+    #[must_use]
+    pub fn is_dir(&self) -> bool {
+        self.name.ends_with('/')
+    }
+
+    /// last component of the path
+    #[must_use]
+    pub fn file_name(&self) -> &str {
+        self.name.rsplit('/').next().unwrap_or_default()
+    }
+}
+
+/// A zip archive held in memory.
+pub struct Archive<'a> {
+    data: &'a [u8],
+    entries: Vec<Entry>,
+}
+
+impl<'a> Archive<'a> {
+    /// Parse the central directory of an archive.
+    ///
+    /// # Errors
+    ///   raises `std::io::Error` of kind `InvalidData` if the archive is malformed or uses an unsupported feature
+    pub fn new(data: &'a [u8]) -> io::Result<Self> {
+        let end = find_end_of_central_directory(data)?;
+        let mut reader = Reader::at(data, end + 4);
+        let (disk, directory_disk) = (reader.u16()?, reader.u16()?);
+        let (_, count) = (reader.u16()?, reader.u16()?);
+        let (_, directory_offset) = (reader.u32()?, reader.u32()?);
+        if disk != 0 || directory_disk != 0 {
+            return Err(invalid("multi-disk archives are not supported"));
+        }
+        if count == u16::MAX || directory_offset == u32::MAX {
+            return Err(invalid("zip64 archives are not supported"));
+        }
+
+        let mut reader = Reader::at(data, directory_offset as usize);
+        let entries = (0..count)
+            .map(|_| {
+                if reader.u32()? != CENTRAL_HEADER_SIGNATURE {
+                    return Err(invalid("bad central directory header"));
+                }
+                reader.skip(4)?; // version made by, version needed
+                let (flags, method) = (reader.u16()?, reader.u16()?);
+                reader.skip(4)?; // modification time and date
+                let (crc32, compressed_size, size) = (reader.u32()?, reader.u32()?, reader.u32()?);
+                let (name_length, extra_length, comment_length) =
+                    (reader.u16()?, reader.u16()?, reader.u16()?);
+                reader.skip(8)?; // disk number, internal and external attributes
+                let local_header_offset = reader.u32()?;
+                let name =
+                    String::from_utf8_lossy(reader.bytes(usize::from(name_length))?).into_owned();
+                reader.skip(usize::from(extra_length) + usize::from(comment_length))?;
+
+                if compressed_size == u32::MAX
+                    || size == u32::MAX
+                    || local_header_offset == u32::MAX
+                {
+                    return Err(invalid(format!("{name}: zip64 entries are not supported")));
+                }
+                Ok(Entry {
+                    name,
+                    method,
+                    flags,
+                    crc32,
+                    compressed_size: compressed_size as usize,
+                    size: size as usize,
+                    local_header_offset: local_header_offset as usize,
+                })
+            })
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self { data, entries })
+    }
+
+    #[must_use]
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Decompress an entry and verify its checksum.
+    ///
+    /// # Errors
+    ///   raises `std::io::Error` of kind `InvalidData` if the entry is corrupt, encrypted or uses an unsupported method
+    pub fn read(&self, entry: &Entry) -> io::Result<Vec<u8>> {
+        if entry.flags & 1 != 0 {
+            return Err(invalid(format!(
+                "{}: encrypted entries are not supported",
+                entry.name
+            )));
+        }
+
+        let mut reader = Reader::at(self.data, entry.local_header_offset);
+        if reader.u32()? != LOCAL_HEADER_SIGNATURE {
+            return Err(invalid(format!("{}: bad local header", entry.name)));
+        }
+        // the local header repeats the central directory, but sizes may be deferred to a data descriptor
+        reader.skip(22)?;
+        let (name_length, extra_length) = (reader.u16()?, reader.u16()?);
+        reader.skip(usize::from(name_length) + usize::from(extra_length))?;
+        let compressed = reader.bytes(entry.compressed_size)?;
+
+        let contents = match entry.method {
+            METHOD_STORED => compressed.to_vec(),
+            METHOD_DEFLATE => inflate(compressed, entry.size)?,
+            method => {
+                return Err(invalid(format!(
+                    "{}: unsupported compression method {method}",
+                    entry.name
+                )))
+            }
+        };
+        if contents.len() != entry.size {
+            return Err(invalid(format!("{}: size mismatch", entry.name)));
+        }
+        if crc32(&contents) != entry.crc32 {
+            return Err(invalid(format!("{}: checksum mismatch", entry.name)));
+        }
+        Ok(contents)
+    }
+}
+
+/// the record is the last thing in the archive, but may be followed by a variable-length comment
+fn find_end_of_central_directory(data: &[u8]) -> io::Result<usize> {
+    let last = data
+        .len()
+        .checked_sub(END_OF_CENTRAL_DIRECTORY_SIZE)
+        .ok_or_else(|| invalid("archive too short"))?;
+    (last.saturating_sub(usize::from(u16::MAX))..=last)
+        .rev()
+        .find(|&offset| {
+            data[offset..offset + 4] == END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes()
+        })
+        .ok_or_else(|| invalid("end of central directory not found"))
+}
+
+/// CRC-32 (IEEE 802.3, reflected), as used by zip and gzip
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn crc32(data: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0; 256];
+        let mut idx = 0;
+        while idx < 256 {
+            let mut crc = idx as u32;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = if crc & 1 == 1 {
+                    0xedb8_8320 ^ (crc >> 1)
+                } else {
+                    crc >> 1
+                };
+                bit += 1;
+            }
+            table[idx] = crc;
+            idx += 1;
+        }
+        table
+    };
+
+    !data.iter().fold(u32::MAX, |crc, &byte| {
+        TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// little-endian cursor which errors instead of panicking on truncated input
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn at(data: &'a [u8], position: usize) -> Self {
+        Self { data, position }
+    }
+
+    fn bytes(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        let bytes = self
+            .data
+            .get(self.position..self.position.saturating_add(len))
+            .ok_or_else(|| invalid("unexpected end of archive"))?;
+        self.position += len;
+        Ok(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> io::Result<()> {
+        self.bytes(len).map(drop)
+    }
+
+    fn u16(&mut self) -> io::Result<u16> {
+        self.bytes(2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> io::Result<u32> {
+        self.bytes(4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+}
+
+/// Build an archive of stored entries, used to create fixtures in tests.
+#[cfg(test)]
+pub(crate) fn build_stored(files: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut archive = vec![];
+    let mut directory = vec![];
+    for &(name, contents) in files {
+        let offset = u32::try_from(archive.len()).unwrap();
+        let size = u32::try_from(contents.len()).unwrap().to_le_bytes();
+        let name_length = u16::try_from(name.len()).unwrap().to_le_bytes();
+        let crc = crc32(contents).to_le_bytes();
+
+        archive.extend(LOCAL_HEADER_SIGNATURE.to_le_bytes());
+        archive.extend([20, 0, 0, 0, 0, 0, 0, 0, 0, 0]); // version, flags, method, time, date
+        archive.extend(crc.iter().chain(&size).chain(&size).chain(&name_length));
+        archive.extend([0, 0]);
+        archive.extend(name.as_bytes());
+        archive.extend(contents);
+
+        directory.extend(CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+        directory.extend([20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0]); // versions, flags, method, time, date
+        directory.extend(crc.iter().chain(&size).chain(&size).chain(&name_length));
+        directory.extend([0; 12]); // extra and comment lengths, disk, attributes
+        directory.extend(offset.to_le_bytes());
+        directory.extend(name.as_bytes());
+    }
+
+    let count = u16::try_from(files.len()).unwrap().to_le_bytes();
+    let directory_offset = u32::try_from(archive.len()).unwrap().to_le_bytes();
+    let directory_size = u32::try_from(directory.len()).unwrap().to_le_bytes();
+    archive.extend(directory);
+    archive.extend(END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+    archive.extend([0, 0, 0, 0]);
+    archive.extend(
+        count
+            .iter()
+            .chain(&count)
+            .chain(&directory_size)
+            .chain(&directory_offset),
+    );
+    archive.extend([0, 0]);
+    archive
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fmt::Write;
+
+    /// Built with Python's `zipfile` at the default deflate level:
+    /// `1.in` = "3\n1 2 3\n", `1.out` = "6\n" (stored), `2.in` = `"{i} {i * i}\n"` for i in 0..5000 (dynamic
+    /// Huffman blocks with long back references), `2.out` = 4096 bytes of `random.Random(7).randbytes` (incompressible),
+    /// and an empty `tests/` directory entry.
+    const FIXTURE: &[u8] = include_bytes!("../tests/fixtures/cses_tests.zip");
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn test_fixture_entries() {
+        let archive = Archive::new(FIXTURE).unwrap();
+        let names: Vec<&str> = archive.entries().iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "tests/",
+                "tests/1.in",
+                "tests/1.out",
+                "tests/2.in",
+                "tests/2.out"
+            ]
+        );
+        assert!(archive.entries()[0].is_dir());
+        assert_eq!(archive.entries()[1].file_name(), "1.in");
+    }
+
+    #[test]
+    fn test_fixture_contents() {
+        let archive = Archive::new(FIXTURE).unwrap();
+        let contents: Vec<Vec<u8>> = archive
+            .entries()
+            .iter()
+            .map(|e| archive.read(e).unwrap())
+            .collect();
+        assert_eq!(contents[1], b"3\n1 2 3\n");
+        assert_eq!(contents[2], b"6\n");
+        let mut squares = String::new();
+        for i in 0..5000_u64 {
+            writeln!(squares, "{i} {}", i * i).unwrap();
+        }
+        assert_eq!(contents[3], squares.as_bytes());
+        // the checksum is verified by `read`, so only the length is left to check
+        assert_eq!(contents[4].len(), 4096);
+    }
+
+    #[test]
+    fn test_stored_roundtrip() {
+        let data = build_stored(&[
+            ("1.in", b"7\n"),
+            ("1.out", b"2 4 6 1 5 3 7\n"),
+            ("empty", b""),
+        ]);
+        let archive = Archive::new(&data).unwrap();
+        let contents: Vec<Vec<u8>> = archive
+            .entries()
+            .iter()
+            .map(|e| archive.read(e).unwrap())
+            .collect();
+        assert_eq!(contents, [&b"7\n"[..], b"2 4 6 1 5 3 7\n", b""]);
+    }
+
+    #[test]
+    fn test_corrupt_archive() {
+        assert!(Archive::new(b"not a zip").is_err());
+
+        let mut data = build_stored(&[("1.in", b"7\n")]);
+        // flip a byte of the contents, which sit right after the 30-byte local header and the name
+        data[34] ^= 1;
+        let archive = Archive::new(&data).unwrap();
+        assert!(archive.read(&archive.entries()[0]).is_err());
+    }
+}
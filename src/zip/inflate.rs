@@ -0,0 +1,334 @@
+//! DEFLATE decoder (RFC 1951), following the canonical-Huffman approach of zlib's `puff.c`.
+//!
+//! Decoding speed is not a priority here, test archives are at most a few megabytes.
+
+use std::io;
+
+/// maximum bits in a Huffman code
+const MAX_BITS: usize = 15;
+
+/// base lengths for length symbols 257..=285
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+/// base distances for distance symbols 0..=29
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+/// order in which code length code lengths are stored in a dynamic block header
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("deflate: {message}"))
+}
+
+/// LSB-first bit reader over the compressed stream
+struct Bits<'a> {
+    data: &'a [u8],
+    position: usize,
+    buffer: u32,
+    count: u32,
+}
+
+impl<'a> Bits<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            position: 0,
+            buffer: 0,
+            count: 0,
+        }
+    }
+
+    fn take(&mut self, need: u32) -> io::Result<u32> {
+        while self.count < need {
+            let byte = *self
+                .data
+                .get(self.position)
+                .ok_or_else(|| invalid("unexpected end of stream"))?;
+            self.position += 1;
+            self.buffer |= u32::from(byte) << self.count;
+            self.count += 8;
+        }
+        let value = self.buffer & ((1 << need) - 1);
+        self.buffer >>= need;
+        self.count -= need;
+        Ok(value)
+    }
+
+    /// drop the remaining bits of the current byte, stored blocks are byte-aligned
+    fn align(&mut self) {
+        self.buffer = 0;
+        self.count = 0;
+    }
+}
+
+/// canonical Huffman code, stored as the number of codes per length and the symbols sorted by code
+struct Huffman {
+    counts: [u16; MAX_BITS + 1],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    /// build from the code length of every symbol, rejecting over-subscribed codes.
+    /// Incomplete codes are allowed, as a single distance code is legal.
+    fn new(lengths: &[u8]) -> io::Result<Self> {
+        let mut counts = [0_u16; MAX_BITS + 1];
+        for &length in lengths {
+            counts[usize::from(length)] += 1;
+        }
+        counts[0] = 0;
+
+        let mut left: i32 = 1;
+        for &count in &counts[1..] {
+            left = (left << 1) - i32::from(count);
+            if left < 0 {
+                return Err(invalid("over-subscribed huffman code"));
+            }
+        }
+
+        let mut offsets = [0_u16; MAX_BITS + 2];
+        for length in 1..=MAX_BITS {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+        let mut symbols = vec![0; usize::from(offsets[MAX_BITS + 1])];
+        for (symbol, &length) in (0_u16..).zip(lengths) {
+            if length != 0 {
+                let offset = &mut offsets[usize::from(length)];
+                symbols[usize::from(*offset)] = symbol;
+                *offset += 1;
+            }
+        }
+        Ok(Self { counts, symbols })
+    }
+
+    fn decode(&self, bits: &mut Bits) -> io::Result<u16> {
+        // code: bits read so far, first: first code of the current length, index: first symbol of the current length
+        let (mut code, mut first, mut index) = (0_i32, 0_i32, 0_i32);
+        for &count in &self.counts[1..] {
+            code |= i32::try_from(bits.take(1)?).unwrap_or_default();
+            let count = i32::from(count);
+            if code - count < first {
+                return Ok(self.symbols[usize::try_from(index + code - first).unwrap_or_default()]);
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        Err(invalid("invalid huffman code"))
+    }
+}
+
+/// Decompress a raw DEFLATE stream (no zlib or gzip header). `size_hint` is only used to reserve the output, up to
+/// what `data` can possibly expand to, since it comes from an untrusted header.
+///
+/// # Errors
+///   raises `std::io::Error` of kind `InvalidData` if the stream is malformed or truncated
+pub fn inflate(data: &[u8], size_hint: usize) -> io::Result<Vec<u8>> {
+    /// a 258-byte match costs at least 2 bits, so a byte expands to at most 1032 bytes
+    const MAX_EXPANSION: usize = 1032;
+
+    let mut out = Vec::with_capacity(size_hint.min(data.len().saturating_mul(MAX_EXPANSION)));
+    let mut bits = Bits::new(data);
+    loop {
+        let last = bits.take(1)? == 1;
+        match bits.take(2)? {
+            0 => stored(&mut bits, &mut out)?,
+            1 => {
+                let (lengths, distances) = fixed_codes()?;
+                codes(&mut bits, &mut out, &lengths, &distances)?;
+            }
+            2 => {
+                let (lengths, distances) = dynamic_codes(&mut bits)?;
+                codes(&mut bits, &mut out, &lengths, &distances)?;
+            }
+            _ => return Err(invalid("invalid block type")),
+        }
+        if last {
+            return Ok(out);
+        }
+    }
+}
+
+fn stored(bits: &mut Bits, out: &mut Vec<u8>) -> io::Result<()> {
+    bits.align();
+    let header = bits
+        .data
+        .get(bits.position..bits.position + 4)
+        .ok_or_else(|| invalid("truncated stored block"))?;
+    let length = usize::from(u16::from_le_bytes([header[0], header[1]]));
+    let complement = usize::from(u16::from_le_bytes([header[2], header[3]]));
+    if length != !complement & 0xffff {
+        return Err(invalid("stored block length does not match its complement"));
+    }
+    bits.position += 4;
+    let block = bits
+        .data
+        .get(bits.position..bits.position + length)
+        .ok_or_else(|| invalid("truncated stored block"))?;
+    out.extend_from_slice(block);
+    bits.position += length;
+    Ok(())
+}
+
+fn fixed_codes() -> io::Result<(Huffman, Huffman)> {
+    let mut lengths = [0_u8; 288];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+    Ok((Huffman::new(&lengths)?, Huffman::new(&[5; 30])?))
+}
+
+fn dynamic_codes(bits: &mut Bits) -> io::Result<(Huffman, Huffman)> {
+    let literal_count = bits.take(5)? as usize + 257;
+    let distance_count = bits.take(5)? as usize + 1;
+    let code_length_count = bits.take(4)? as usize + 4;
+    if literal_count > 286 || distance_count > 30 {
+        return Err(invalid("too many length or distance codes"));
+    }
+
+    let mut code_lengths = [0_u8; 19];
+    for &symbol in &CODE_LENGTH_ORDER[..code_length_count] {
+        code_lengths[symbol] = u8::try_from(bits.take(3)?).unwrap_or_default();
+    }
+    let code_length_code = Huffman::new(&code_lengths)?;
+
+    let mut lengths = vec![0_u8; literal_count + distance_count];
+    let mut index = 0;
+    while index < lengths.len() {
+        let symbol = code_length_code.decode(bits)?;
+        let (value, repeat) = match symbol {
+            0..=15 => (u8::try_from(symbol).unwrap_or_default(), 1),
+            16 => {
+                let previous = *index
+                    .checked_sub(1)
+                    .and_then(|prev| lengths.get(prev))
+                    .ok_or_else(|| invalid("repeat with no previous length"))?;
+                (previous, 3 + bits.take(2)? as usize)
+            }
+            17 => (0, 3 + bits.take(3)? as usize),
+            _ => (0, 11 + bits.take(7)? as usize),
+        };
+        lengths
+            .get_mut(index..index + repeat)
+            .ok_or_else(|| invalid("too many code lengths"))?
+            .fill(value);
+        index += repeat;
+    }
+    if lengths[256] == 0 {
+        return Err(invalid("missing end-of-block code"));
+    }
+
+    let (literal_lengths, distance_lengths) = lengths.split_at(literal_count);
+    Ok((
+        Huffman::new(literal_lengths)?,
+        Huffman::new(distance_lengths)?,
+    ))
+}
+
+fn codes(
+    bits: &mut Bits,
+    out: &mut Vec<u8>,
+    lengths: &Huffman,
+    distances: &Huffman,
+) -> io::Result<()> {
+    loop {
+        let symbol = usize::from(lengths.decode(bits)?);
+        match symbol {
+            0..=255 => out.push(u8::try_from(symbol).unwrap_or_default()),
+            256 => return Ok(()),
+            _ => {
+                let symbol = symbol - 257;
+                if symbol >= LENGTH_BASE.len() {
+                    return Err(invalid("invalid length symbol"));
+                }
+                let length = usize::from(LENGTH_BASE[symbol])
+                    + bits.take(u32::from(LENGTH_EXTRA[symbol]))? as usize;
+
+                let symbol = usize::from(distances.decode(bits)?);
+                if symbol >= DIST_BASE.len() {
+                    return Err(invalid("invalid distance symbol"));
+                }
+                let distance = usize::from(DIST_BASE[symbol])
+                    + bits.take(u32::from(DIST_EXTRA[symbol]))? as usize;
+                if distance > out.len() {
+                    return Err(invalid("distance too far back"));
+                }
+
+                // the copy may overlap the bytes it produces, so it has to go byte by byte
+                let start = out.len() - distance;
+                for idx in start..start + length {
+                    out.push(out[idx]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_stored_block() {
+        // final stored block, length 5, complement of 5, then the raw bytes
+        let data = [0x01, 0x05, 0x00, 0xfa, 0xff, b'h', b'e', b'l', b'l', b'o'];
+        assert_eq!(inflate(&data, 0).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_fixed_block() {
+        // zlib.compressobj(9, zlib.DEFLATED, -15).compress(b"abcabcabcabc\n")
+        let data = [0x4b, 0x4c, 0x4a, 0x4e, 0x84, 0x21, 0x2e, 0x00];
+        assert_eq!(inflate(&data, 0).unwrap(), b"abcabcabcabc\n");
+    }
+
+    #[test]
+    fn test_huge_size_hint() {
+        // a corrupt central directory may claim any size, it must not be allocated up front
+        let data = [0x4b, 0x4c, 0x4a, 0x4e, 0x84, 0x21, 0x2e, 0x00];
+        let out = inflate(&data, usize::MAX).unwrap();
+        assert_eq!(out, b"abcabcabcabc\n");
+        assert!(out.capacity() <= data.len() * 1032);
+    }
+
+    #[test]
+    fn test_empty_fixed_block() {
+        assert_eq!(inflate(&[0x03, 0x00], 0).unwrap(), b"");
+    }
+
+    #[test]
+    fn test_malformed() {
+        // stored block with a bad complement
+        assert!(inflate(&[0x01, 0x05, 0x00, 0x00, 0x00], 0).is_err());
+        // reserved block type
+        assert!(inflate(&[0x07], 0).is_err());
+        // back reference before the start of the output
+        assert!(inflate(&[0x03, 0x02, 0x00], 0).is_err());
+        assert!(inflate(&[], 0).is_err());
+    }
+
+    #[test]
+    fn test_garbage_never_panics() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(1951);
+        for _ in 0..20_000 {
+            let len = rng.gen_range(0..64);
+            let data: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            let _ = inflate(&data, 0);
+        }
+    }
+}
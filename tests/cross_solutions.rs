@@ -0,0 +1,188 @@
+//! Differential verification between bins solving overlapping problems.
+//!
+//! Each pair runs the real executables on random (validated) inputs, and the answer of one problem is derived from the
+//! output of the other:
+//! - the Josephus queries are positions in the removal order of Josephus Problem I
+//! - Josephus Problem II with k = 1 is Josephus Problem I
+//! - Subarray Sums II agrees with Subarray Sums I on positive arrays
+//! - Message Route finds shortest paths of Shortest Routes I with unit weights
+
+use std::fmt::Write;
+use std::process::{Command, Stdio};
+
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+/// validate the input for the bin, run its executable and return the whitespace-separated tokens it printed
+macro_rules! run {
+    ($bin:ident, $input:expr) => {
+        // `match` keeps temporaries like `format!(..).as_bytes()` alive
+        match $input {
+            input => {
+                rust_cses::validate::assert_valid(stringify!($bin), input);
+                run(env!(concat!("CARGO_BIN_EXE_", stringify!($bin))), input)
+            }
+        }
+    };
+}
+
+fn run(executable: &str, input: &[u8]) -> Vec<String> {
+    let mut child = Command::new(executable)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    std::io::Write::write_all(&mut child.stdin.take().unwrap(), input).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{executable} failed");
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .split_ascii_whitespace()
+        .map(str::to_string)
+        .collect()
+}
+
+fn numbers(tokens: &[String]) -> Vec<u64> {
+    tokens.iter().map(|token| token.parse().unwrap()).collect()
+}
+
+/// sizes to check: every small one, then random ones up to the limit
+fn sizes(rng: &mut StdRng, small: u64, random: usize) -> Vec<u64> {
+    let mut sizes: Vec<u64> = (1..=small).collect();
+    sizes.extend((0..random).map(|_| rng.gen_range(small..=200_000)));
+    sizes
+}
+
+#[test]
+fn test_josephus_queries_vs_problem_1() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for n in sizes(&mut rng, 30, 4) {
+        let order = numbers(&run!(
+            search_josephus_problem_1,
+            format!("{n}\n").as_bytes()
+        ));
+
+        let ks: Vec<u64> = if n <= 30 {
+            (1..=n).collect()
+        } else {
+            (0..1000).map(|_| rng.gen_range(1..=n)).collect()
+        };
+        let mut input = format!("{}\n", ks.len());
+        for k in &ks {
+            writeln!(input, "{n} {k}").unwrap();
+        }
+        let answers = numbers(&run!(mathematics_josephus_queries, input.as_bytes()));
+
+        assert_eq!(answers.len(), ks.len());
+        for (k, answer) in ks.iter().zip(answers) {
+            assert_eq!(
+                answer,
+                order[usize::try_from(k - 1).unwrap()],
+                "n = {n}, k = {k}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_josephus_problem_2_vs_problem_1() {
+    let mut rng = StdRng::seed_from_u64(2);
+    for n in sizes(&mut rng, 30, 4) {
+        let first = run!(search_josephus_problem_1, format!("{n}\n").as_bytes());
+        let second = run!(search_josephus_problem_2, format!("{n} 1\n").as_bytes());
+        assert_eq!(first, second, "n = {n}");
+    }
+}
+
+#[test]
+fn test_subarray_sums_2_vs_1() {
+    let mut rng = StdRng::seed_from_u64(3);
+    for case in 0..300 {
+        let n = if case < 290 {
+            rng.gen_range(1..=12)
+        } else {
+            rng.gen_range(1..=200_000)
+        };
+        // small values so that many subarrays hit the target
+        let max = rng.gen_range(1..=5);
+        let values: Vec<String> = (0..n).map(|_| rng.gen_range(1..=max).to_string()).collect();
+        let x = rng.gen_range(1..=(n * max).min(1000));
+        let input = format!("{n} {x}\n{}\n", values.join(" "));
+
+        let positive = run!(search_subarray_sums_1, input.as_bytes());
+        let any = run!(search_subarray_sums_2, input.as_bytes());
+        assert_eq!(positive, any, "{input}");
+    }
+}
+
+/// a random simple undirected graph, connected or not
+fn random_graph(rng: &mut StdRng, n: usize) -> Vec<(usize, usize)> {
+    let mut pairs: Vec<(usize, usize)> = (1..=n)
+        .flat_map(|a| (a + 1..=n).map(move |b| (a, b)))
+        .collect();
+    pairs.shuffle(rng);
+    let m = rng.gen_range(1..=pairs.len().min(3 * n));
+    pairs.truncate(m);
+    pairs
+}
+
+#[test]
+fn test_message_route_vs_shortest_routes_1() {
+    let mut rng = StdRng::seed_from_u64(4);
+    for _ in 0..150 {
+        let n = rng.gen_range(2..=25);
+        let edges = random_graph(&mut rng, n);
+
+        // both directions with unit weights, plus a flight of length n from city 1 to every city: it keeps every city
+        // reachable as Shortest Routes I requires, and is longer than any path of the unit graph
+        let mut input = format!("{} {}\n", n, 2 * edges.len() + n - 1);
+        for &(a, b) in &edges {
+            writeln!(input, "{a} {b} 1\n{b} {a} 1").unwrap();
+        }
+        for city in 2..=n {
+            writeln!(input, "1 {city} {n}").unwrap();
+        }
+        let distances = numbers(&run!(graph_shortest_routes_1, input.as_bytes()));
+        assert_eq!(distances.len(), n);
+
+        // Message Route always goes to computer n, relabel so that the target takes its place
+        let mut targets: Vec<usize> = (2..n).collect();
+        targets.shuffle(&mut rng);
+        targets.truncate(2);
+        targets.push(n);
+        for target in targets {
+            let relabel = |city: usize| match city {
+                city if city == target => n,
+                city if city == n => target,
+                city => city,
+            };
+            let mut input = format!("{n} {}\n", edges.len());
+            for &(a, b) in &edges {
+                writeln!(input, "{} {}", relabel(a), relabel(b)).unwrap();
+            }
+            let route = run!(graph_message_route, input.as_bytes());
+
+            let distance = distances[target - 1];
+            if distance >= n as u64 {
+                assert_eq!(route, ["IMPOSSIBLE"], "{input}");
+                continue;
+            }
+            let route = numbers(&route);
+            assert_eq!(route[0], distance + 1, "{input}");
+            let path: Vec<usize> = route[1..]
+                .iter()
+                .map(|&city| usize::try_from(city).unwrap())
+                .collect();
+            assert_eq!(path.len() as u64, route[0], "{input}");
+            assert_eq!((path[0], path[path.len() - 1]), (1, n), "{input}");
+            for hop in path.windows(2) {
+                let (a, b) = (relabel(hop[0]), relabel(hop[1]));
+                assert!(
+                    edges.contains(&(a.min(b), a.max(b))),
+                    "{input}: no connection between {} and {}",
+                    hop[0],
+                    hop[1]
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Differential verification of the I/O boilerplate in `0_cses_template.rs`, which every bin copies.
+//!
+//! Every integer is written through `itoap` and `CustomBufWriter`, compared against `to_string()`, then parsed back
+//! through the optimistic parsers. Both the sse2 and the fallback `itoap` backends are checked independently.
+
+#[allow(dead_code)]
+#[path = "../src/bin/0_cses_template.rs"]
+mod template;
+
+use std::fmt::{Debug, Display};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use template::{itoap, AnyFloat, AnyInt, CustomBufWriter, PosInt};
+
+/// number of random values checked per integer type
+const RANDOM_COUNT: usize = 1 << 18;
+
+/// every power of ten and power of two representable in `T` (plus or minus one), and the type boundaries
+macro_rules! boundaries {
+    ($t:ty) => {{
+        let mut values: Vec<$t> = vec![<$t>::MIN, <$t>::MIN + 1, <$t>::MAX - 1, <$t>::MAX, 0, 1];
+        let mut pow: $t = 1;
+        while let Some(next) = pow.checked_mul(10) {
+            pow = next;
+            values.extend([pow - 1, pow, pow + 1]);
+        }
+        for shift in 1..<$t>::BITS - 1 {
+            let pow: $t = 1 << shift;
+            values.extend([pow - 1, pow, pow + 1]);
+        }
+        #[allow(unused_comparisons)]
+        if <$t>::MIN < 0 {
+            let negated: Vec<$t> = values.iter().filter_map(|v| v.checked_neg()).collect();
+            values.extend(negated);
+        }
+        values
+    }};
+}
+
+/// uniformly random bits, then shifted so that every digit count is well represented
+macro_rules! random {
+    ($t:ty, $rng:expr) => {{
+        let rng: &mut StdRng = $rng;
+        (0..RANDOM_COUNT)
+            .map(|_| rng.gen::<$t>() >> rng.gen_range(0..<$t>::BITS))
+            .collect::<Vec<$t>>()
+    }};
+}
+
+/// write every value through both writers and compare against the standard formatter
+fn check_write<T: itoap::Integer + Display + Copy>(values: &[T]) {
+    let mut expected = String::new();
+    let mut vec = vec![];
+    for &value in values {
+        let target = value.to_string();
+        itoap::write_to_vec(&mut vec, value);
+        assert_eq!(std::str::from_utf8(&vec).unwrap(), target);
+        vec.clear();
+
+        expected.push_str(&target);
+        expected.push(' ');
+    }
+
+    let mut out = Vec::with_capacity(expected.len());
+    {
+        let mut writer = CustomBufWriter::new(&mut out);
+        for &value in values {
+            writer.maybe_flush(41);
+            writer.add_int(value);
+            writer.add_byte(b' ');
+        }
+    }
+    assert_eq!(String::from_utf8(out).unwrap(), expected);
+}
+
+fn check_posint<T: PosInt + Display + PartialEq + Debug + Copy>(values: &[T]) {
+    for &value in values {
+        assert_eq!(T::to_posint(value.to_string().as_bytes()), value);
+    }
+}
+
+fn check_anyint<T: AnyInt + Display + PartialEq + Debug + Copy>(values: &[T]) {
+    for &value in values {
+        assert_eq!(T::to_anyint(value.to_string().as_bytes()), value);
+    }
+}
+
+macro_rules! test_unsigned {
+    ($($name:ident: $t:ty, $seed:expr;)+) => {
+        $(#[test]
+        fn $name() {
+            let values = boundaries!($t);
+            check_write(&values);
+            check_posint(&values);
+
+            let values = random!($t, &mut StdRng::seed_from_u64($seed));
+            check_write(&values);
+            check_posint(&values);
+        })+
+    };
+}
+
+macro_rules! test_signed {
+    ($($name:ident: $t:ty, $seed:expr;)+) => {
+        $(#[test]
+        fn $name() {
+            let values = boundaries!($t);
+            check_write(&values);
+            check_anyint(&values);
+            check_posint(&values.iter().copied().filter(|&v| v >= 0).collect::<Vec<_>>());
+
+            let values = random!($t, &mut StdRng::seed_from_u64($seed));
+            check_write(&values);
+            check_anyint(&values);
+        })+
+    };
+}
+
+test_unsigned! {
+    test_u8: u8, 8;
+    test_u16: u16, 16;
+    test_u32: u32, 32;
+    test_u64: u64, 64;
+    test_u128: u128, 128;
+    test_usize: usize, 1;
+}
+
+test_signed! {
+    test_i8: i8, 108;
+    test_i16: i16, 116;
+    test_i32: i32, 132;
+    test_i64: i64, 164;
+    test_i128: i128, 228;
+    test_isize: isize, 101;
+}
+
+#[test]
+fn test_u8_exhaustive() {
+    let values: Vec<u8> = (0..=u8::MAX).collect();
+    check_write(&values);
+    check_posint(&values);
+}
+
+#[test]
+fn test_i16_exhaustive() {
+    let values: Vec<i16> = (i16::MIN..=i16::MAX).collect();
+    check_write(&values);
+    check_anyint(&values);
+}
+
+/// `usize` is dispatched to the writer of matching width, it must agree with the fixed-width type
+#[test]
+fn test_usize_width() {
+    #[cfg(target_pointer_width = "64")]
+    type Fixed = u64;
+    #[cfg(target_pointer_width = "32")]
+    type Fixed = u32;
+    #[cfg(target_pointer_width = "16")]
+    type Fixed = u16;
+
+    assert_eq!(
+        <usize as itoap::Integer>::MAX_LEN,
+        <Fixed as itoap::Integer>::MAX_LEN
+    );
+    assert_eq!(
+        <isize as itoap::Integer>::MAX_LEN,
+        <Fixed as itoap::Integer>::MAX_LEN + 1
+    );
+    for value in boundaries!(usize) {
+        let (mut sized, mut fixed) = (vec![], vec![]);
+        itoap::write_to_vec(&mut sized, value);
+        itoap::write_to_vec(&mut fixed, value as Fixed);
+        assert_eq!(sized, fixed);
+    }
+}
+
+/// call a raw backend writer, checking the returned length matches what the standard formatter produces
+macro_rules! backend_write {
+    ($func:path, $value:expr) => {{
+        let mut buf = [0_u8; 40];
+        let len = unsafe { $func($value, buf.as_mut_ptr()) };
+        String::from_utf8(buf[..len].to_vec()).unwrap()
+    }};
+}
+
+fn backend_values(seed: u64) -> (Vec<u32>, Vec<u64>) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut small = boundaries!(u32);
+    small.extend(random!(u32, &mut rng));
+    let mut large = boundaries!(u64);
+    large.extend(random!(u64, &mut rng));
+    (small, large)
+}
+
+#[test]
+fn test_fallback_backend() {
+    let (small, large) = backend_values(2);
+    for value in small {
+        assert_eq!(
+            backend_write!(itoap::fallback::write_u32, value),
+            value.to_string()
+        );
+    }
+    for value in large {
+        assert_eq!(
+            backend_write!(itoap::fallback::write_u64, value),
+            value.to_string()
+        );
+    }
+}
+
+#[cfg(all(
+    any(target_arch = "x86_64", target_arch = "x86"),
+    target_feature = "sse2",
+    not(miri),
+))]
+#[test]
+fn test_sse2_backend() {
+    let (small, large) = backend_values(2);
+    for value in small {
+        assert_eq!(
+            backend_write!(itoap::sse2::write_u32, value),
+            value.to_string()
+        );
+    }
+    for value in large {
+        assert_eq!(
+            backend_write!(itoap::sse2::write_u64, value),
+            value.to_string()
+        );
+    }
+}
+
+/// `to_float` is not correctly rounded, so only check that it lands within a few ulps of the standard parser
+#[test]
+fn test_float_roundtrip() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut out = vec![];
+    for _ in 0..RANDOM_COUNT {
+        let value: f64 = rng.gen_range(-1e9..1e9);
+        let decimals = rng.gen_range(0..=9);
+        {
+            let mut writer = CustomBufWriter::new(&mut out);
+            writer.add_float(value, decimals);
+        }
+        let target: f64 = std::str::from_utf8(&out).unwrap().parse().unwrap();
+        let parsed = f64::to_float(&out);
+        assert!(
+            (parsed - target).abs() <= target.abs() * 1e-14 + 1e-15,
+            "{} parsed as {parsed}",
+            std::str::from_utf8(&out).unwrap()
+        );
+        out.clear();
+    }
+}
@@ -0,0 +1,103 @@
+//! The in-process solvers of the library must behave exactly like the bins they are compiled from.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use rust_cses::registry::{problem_set, solutions};
+use rust_cses::solutions::{solve_by_name, solver, SOLVED_BINS};
+
+fn bin_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src/bin")
+}
+
+/// the `let input = b"\` literals of a bin's tests, skipping the ones with escapes
+fn test_inputs(source: &str) -> Vec<&str> {
+    source
+        .split("let input = b\"\\\n")
+        .skip(1)
+        .filter_map(|rest| rest.split_once("\";").map(|(input, _)| input))
+        .filter(|input| !input.contains('\\'))
+        .collect()
+}
+
+/// run the executable of `bin`, built alongside this test
+fn run_bin(bin: &str, input: &[u8]) -> Vec<u8> {
+    let executable = std::env::current_exe()
+        .unwrap()
+        .parent()
+        .and_then(Path::parent)
+        .unwrap()
+        .join(bin);
+    let mut child = Command::new(executable)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{bin} failed");
+    output.stdout
+}
+
+#[test]
+fn test_every_solution_is_listed() {
+    let solutions = solutions(&bin_dir(), &problem_set()).unwrap();
+    let runnable: Vec<&str> = solutions
+        .iter()
+        .filter(|solution| solution.doc.interaction.is_empty())
+        .map(|solution| solution.bin.as_str())
+        .collect();
+    for bin in &runnable {
+        assert!(SOLVED_BINS.contains(bin), "{bin} is not in the library");
+    }
+    assert_eq!(SOLVED_BINS.len(), runnable.len());
+    assert!(solver("interactive_hidden_integer_solution").is_none());
+    assert!(solver("0_cses_template").is_none());
+}
+
+#[test]
+fn test_same_output_as_bins() {
+    let mut checked = 0;
+    for bin in SOLVED_BINS {
+        let source = fs::read_to_string(bin_dir().join(format!("{bin}.rs"))).unwrap();
+        for input in test_inputs(&source) {
+            assert_eq!(
+                solve_by_name(bin, input.as_bytes()),
+                run_bin(bin, input.as_bytes()),
+                "{bin} on {input}"
+            );
+            checked += 1;
+        }
+    }
+    assert!(checked > 200, "only {checked} test inputs found");
+}
+
+#[test]
+fn test_solve_by_name() {
+    assert_eq!(
+        solve_by_name("intro_weird_algorithm", b"3\n"),
+        b"3 10 5 16 8 4 2 1\n"
+    );
+    assert_eq!(
+        solve_by_name("intro_palindrome_reorder", b"AAB\n"),
+        b"ABA\n"
+    );
+    // the solver mutates its own copy of the input
+    let input = b"baaa\n".to_vec();
+    assert_eq!(solve_by_name("string_minimal_rotation", &input), b"aaab");
+    assert_eq!(input, b"baaa\n");
+}
+
+#[test]
+#[should_panic(expected = "no solution named search_unknown")]
+fn test_unknown_name() {
+    let _ = solve_by_name("search_unknown", b"1\n");
+}
+
+#[test]
+#[should_panic(expected = "invalid input for intro_weird_algorithm")]
+fn test_invalid_input() {
+    let _ = solve_by_name("intro_weird_algorithm", b"0\n");
+}